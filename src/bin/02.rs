@@ -1,4 +1,4 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse_games);
 
 struct Set {
     blue: u32,
@@ -37,7 +37,7 @@ impl From<&str> for Set {
     }
 }
 
-struct Game {
+pub struct Game {
     number: u32,
     sets: Vec<Set>,
 }
@@ -85,8 +85,11 @@ impl From<&str> for Game {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games: Vec<Game> = input.lines().map(Game::from).collect();
+pub fn parse_games(input: &str) -> Vec<Game> {
+    input.lines().map(Game::from).collect()
+}
+
+pub fn part_one(games: &[Game]) -> Option<u32> {
     let erg = games
         .iter()
         .filter(|game| game.possible(12, 13, 14))
@@ -95,26 +98,13 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(erg)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let games: Vec<Game> = input.lines().map(Game::from).collect();
-    let reds = games
-        .iter()
-        .map(|game| game.sets.iter().map(|set| set.red).max().unwrap())
-        .collect::<Vec<u32>>();
-    let greens = games
-        .iter()
-        .map(|game| game.sets.iter().map(|set| set.green).max().unwrap())
-        .collect::<Vec<u32>>();
-    let blues = games
+pub fn part_two(games: &[Game]) -> Option<u32> {
+    let power = games
         .iter()
-        .map(|game| game.sets.iter().map(|set| set.blue).max().unwrap())
-        .collect::<Vec<u32>>();
-
-    let mut power = Vec::new();
-    for i in 0..reds.len() {
-        power.push(reds[i] * greens[i] * blues[i]);
-    }
-    Some(power.iter().sum())
+        .map(|game| game.fewest_cubes_possible())
+        .map(|set| set.red * set.green * set.blue)
+        .sum();
+    Some(power)
 }
 
 #[cfg(test)]
@@ -157,13 +147,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_games(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_games(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(2286));
     }
 }
@@ -1,10 +1,9 @@
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, parse_cards);
 
-struct Card {
+pub struct Card {
     id: u32,
     winning_numbers: Vec<u32>,
     numbers: Vec<u32>,
-    copies: u32,
 }
 
 impl Card {
@@ -58,31 +57,30 @@ impl From<&str> for Card {
             id,
             winning_numbers,
             numbers,
-            copies: 1,
         }
     }
 }
 
-pub fn part_one(_input: &str) -> Option<u32> {
-    let cards = _input.lines().map(Card::from).collect::<Vec<Card>>();
+pub fn parse_cards(input: &str) -> Vec<Card> {
+    input.lines().map(Card::from).collect()
+}
+
+pub fn part_one(cards: &[Card]) -> Option<u32> {
     let total_points = cards.iter().map(|card| card.points()).sum::<u32>();
     Some(total_points)
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
-    let mut num_cards = 0;
-    let mut cards = _input.lines().map(Card::from).collect::<Vec<Card>>();
-    for i in 0..cards.len() {
-        let matches = cards[i].matches() as usize;
-        for j in 1..=matches {
-            if i + j >= cards.len() {
-                break;
-            }
-            cards[i + j].copies += cards[i].copies;
+pub fn part_two(cards: &[Card]) -> Option<u32> {
+    // every card starts out as a single copy and wins copies of the cards with the next ids.
+    let mut copies = vec![1; cards.len()];
+    for card in cards {
+        let index = card.id as usize - 1;
+        let last_won = (index + card.matches() as usize).min(cards.len() - 1);
+        for won in index + 1..=last_won {
+            copies[won] += copies[index];
         }
-        num_cards += cards[i].copies;
     }
-    Some(num_cards)
+    Some(copies.iter().sum())
 }
 
 #[cfg(test)]
@@ -111,13 +109,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_cards(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_cards(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(30));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse_input);
 
 enum ParserState {
     SeedToSoil,
@@ -44,7 +44,7 @@ impl MyHashMap {
     }
 }
 
-struct Problem {
+pub struct Problem {
     seeds: Vec<u32>,
     seed_to_soil: MyHashMap,
     soil_to_fertilizer: MyHashMap,
@@ -55,7 +55,7 @@ struct Problem {
     humidity_to_location: MyHashMap,
 }
 
-fn parse_input(input: &str) -> Problem {
+pub fn parse_input(input: &str) -> Problem {
     let mut seed_to_soil_hashmap = MyHashMap::new();
     let mut soil_to_fertilizer_hashmap = MyHashMap::new();
     let mut fertilizer_to_water_hashmap = MyHashMap::new();
@@ -137,30 +137,26 @@ fn map_all_seeds(seed: u32, problem: &Problem) -> u32 {
     problem.humidity_to_location.get(humidity)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let problem = parse_input(input);
-
+pub fn part_one(problem: &Problem) -> Option<u32> {
     let min_location = problem
         .seeds
         .iter()
-        .map(|x| map_all_seeds(*x, &problem))
+        .map(|x| map_all_seeds(*x, problem))
         .min()
         .unwrap();
 
     Some(min_location)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let problem = parse_input(input);
-
+pub fn part_two(problem: &Problem) -> Option<u32> {
     let min_location = problem
         .seeds
         .iter()
         .step_by(2)
         .cloned()
         .zip(problem.seeds.iter().skip(1).step_by(2).cloned())
-        .flat_map(|(start, range)| (start..(start + range)))
-        .map(|x| map_all_seeds(x, &problem))
+        .flat_map(|(start, range)| start..(start + range))
+        .map(|x| map_all_seeds(x, problem))
         .min()
         .unwrap();
 
@@ -203,13 +199,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(46));
     }
 }
@@ -50,7 +50,7 @@ fn get_winning_options(max_time: Time, max_distance: Distance) -> u64 {
     for t in 1..=max_time {
         let speed_at_release = t;
         let distance_after_max_time = speed_at_release * (max_time - t);
-        if u64::from(distance_after_max_time) > max_distance {
+        if distance_after_max_time > max_distance {
             winning_options += 1;
         }
     }
@@ -1,30 +1,76 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::{
+    commands::{all, archive, download, leaderboard, read, scaffold, solve, wait},
+    unlock::SystemClock,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{path::PathBuf, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{
+            bench_config::BenchOptions,
+            commands::scaffold::{Mode, ScaffoldOptions},
+            input::{InputSets, InputSource, DEFAULT_INPUT_SET},
+            timeout::TimeoutOptions,
+            year::Year,
+        },
+        Day,
+    };
 
     pub enum AppArguments {
         Download {
             day: Day,
+            year: Year,
+            inputs: Option<InputSets>,
+            force: bool,
         },
         Read {
             day: Day,
+            year: Year,
         },
         Scaffold {
             day: Day,
+            year: Year,
+            template: Option<String>,
+            options: ScaffoldOptions,
         },
         Solve {
             day: Day,
+            year: Year,
             release: bool,
             time: bool,
+            bench: BenchOptions,
+            alloc: bool,
+            timeouts: TimeoutOptions,
+            input: Option<InputSource>,
             submit: Option<u8>,
         },
+        Wait {
+            day: Option<Day>,
+            year: Year,
+        },
+        Archive {
+            next: Option<u16>,
+            dry_run: bool,
+        },
+        Leaderboard {
+            id: Option<u64>,
+            file: Option<PathBuf>,
+            year: Year,
+            day: Option<Day>,
+        },
         All {
+            year: Year,
             release: bool,
             time: bool,
+            bench: BenchOptions,
+            compare: bool,
+            threshold: Option<f64>,
+            alloc: bool,
+            timeouts: TimeoutOptions,
+            inputs: Option<InputSets>,
+            jobs: Option<usize>,
         },
     }
 
@@ -32,24 +78,67 @@ mod args {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
+            Some("all") => {
+                let time = args.contains("--time");
+                let (compare, threshold) = parse_compare_options(&mut args, time)?;
+
+                AppArguments::All {
+                    year: parse_year(&mut args)?,
+                    release: args.contains("--release"),
+                    time,
+                    bench: parse_bench_options(&mut args)?,
+                    compare,
+                    threshold,
+                    alloc: args.contains("--alloc"),
+                    timeouts: parse_timeout_options(&mut args)?,
+                    inputs: args.opt_value_from_str("--inputs")?,
+                    jobs: args.opt_value_from_str("--jobs")?,
+                }
+            }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                year: parse_year(&mut args)?,
+                inputs: args.opt_value_from_str("--inputs")?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                year: parse_year(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
+                year: parse_year(&mut args)?,
+                template: args.opt_value_from_str("--template")?,
+                options: parse_scaffold_options(&mut args)?,
+            },
+            Some("wait") => AppArguments::Wait {
+                // options first, the day is optional.
+                year: parse_year(&mut args)?,
+                day: args.opt_free_from_str()?,
+            },
+            Some("archive") => AppArguments::Archive {
+                next: args.opt_value_from_str("--next")?,
+                dry_run: args.contains("--dry-run"),
+            },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                // options first, the id is optional with --file.
+                file: args.opt_value_from_os_str("--file", |x| {
+                    Ok::<_, std::convert::Infallible>(x.into())
+                })?,
+                year: parse_year(&mut args)?,
+                day: args.opt_value_from_str("--day")?,
+                id: args.opt_free_from_str()?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                bench: parse_bench_options(&mut args)?,
+                alloc: args.contains("--alloc"),
+                timeouts: parse_timeout_options(&mut args)?,
+                input: parse_input_source(&mut args)?,
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -68,6 +157,85 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_bench_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchOptions, pico_args::Error> {
+        Ok(BenchOptions {
+            target_millis: args.opt_value_from_str("--target-time")?,
+            min_samples: args.opt_value_from_str("--min-samples")?,
+            max_samples: args.opt_value_from_str("--max-samples")?,
+            warmup: args.opt_value_from_str("--warmup")?,
+        })
+    }
+
+    /// Runs are compared by their benchmarks, so comparing requires `--time`.
+    fn parse_compare_options(
+        args: &mut pico_args::Arguments,
+        time: bool,
+    ) -> Result<(bool, Option<f64>), Box<dyn std::error::Error>> {
+        let compare = args.contains("--compare");
+        let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+
+        if !time && (compare || threshold.is_some()) {
+            return Err(
+                "--compare and --threshold require --time, e.g. `cargo time --compare`".into(),
+            );
+        }
+
+        if threshold.is_some() && !compare {
+            return Err("--threshold requires --compare".into());
+        }
+
+        Ok((compare, threshold))
+    }
+
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<InputSource>, Box<dyn std::error::Error>> {
+        let file: Option<std::path::PathBuf> =
+            args.opt_value_from_os_str("--input", |x| Ok::<_, std::convert::Infallible>(x.into()))?;
+        let set: Option<String> = args.opt_value_from_str("--input-set")?;
+
+        match (file, set, args.contains("--stdin")) {
+            (None, None, false) => Ok(None),
+            (Some(path), None, false) => Ok(Some(InputSource::File(path))),
+            // the default set is the puzzle input.
+            (None, Some(set), false) if set == DEFAULT_INPUT_SET => Ok(None),
+            (None, Some(set), false) => Ok(Some(InputSource::Set(set))),
+            (None, None, true) => Ok(Some(InputSource::Stdin)),
+            _ => Err("--input, --input-set and --stdin can not be combined".into()),
+        }
+    }
+
+    fn parse_scaffold_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<ScaffoldOptions, Box<dyn std::error::Error>> {
+        let mode = match (args.contains("--force"), args.contains("--repair")) {
+            (false, false) => Mode::Create,
+            (true, false) => Mode::Force,
+            (false, true) => Mode::Repair,
+            (true, true) => return Err("--force and --repair can not be combined".into()),
+        };
+
+        Ok(ScaffoldOptions {
+            mode,
+            dry_run: args.contains("--dry-run"),
+        })
+    }
+
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, pico_args::Error> {
+        Ok(Year::new(args.opt_value_from_str("--year")?))
+    }
+
+    fn parse_timeout_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<TimeoutOptions, pico_args::Error> {
+        Ok(TimeoutOptions {
+            part_secs: args.opt_value_from_str("--part-timeout")?,
+            day_secs: args.opt_value_from_str("--day-timeout")?,
+        })
+    }
 }
 
 fn main() {
@@ -77,16 +245,54 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                time,
+                bench,
+                compare,
+                threshold,
+                alloc,
+                timeouts,
+                inputs,
+                jobs,
+            } => all::handle(
+                year, release, time, bench, compare, threshold, alloc, timeouts, inputs, jobs,
+            ),
+            AppArguments::Download {
+                day,
+                year,
+                inputs,
+                force,
+            } => download::handle(day, year, inputs, force),
+            AppArguments::Read { day, year } => read::handle(day, year),
+            AppArguments::Scaffold {
+                day,
+                year,
+                template,
+                options,
+            } => scaffold::handle(day, year, template.as_deref(), options),
+            AppArguments::Wait { day, year } => wait::handle(year, day, &SystemClock),
+            AppArguments::Archive { next, dry_run } => archive::handle(next, dry_run),
+            AppArguments::Leaderboard {
+                id,
+                file,
+                year,
+                day,
+            } => leaderboard::handle(id, file, year, day),
             AppArguments::Solve {
                 day,
+                year,
                 release,
                 time,
+                bench,
+                alloc,
+                timeouts,
+                input,
                 submit,
-            } => solve::handle(day, release, time, submit),
+            } => solve::handle(
+                day, year, release, time, bench, alloc, timeouts, input, submit,
+            ),
         },
     };
 }
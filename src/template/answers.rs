@@ -0,0 +1,185 @@
+/// Module that reads the accepted answers of a day from `data/answers/{day}.toml` (or the folder of its year), e.g.
+/// ```toml
+/// part_one = "142"
+/// part_two = "281"
+/// ```
+/// Only the flat `key = value` subset of TOML is supported, values can be strings or integers.
+/// Answers of named input sets live in `data/answers/{set}/{day}.toml`.
+use std::{env, fs, io, path::PathBuf};
+
+use crate::template::{input::DEFAULT_INPUT_SET, year::Year};
+use crate::Day;
+
+const ANSWERS_FOLDER: &str = "answers";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The accepted answers of a day.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_one.as_ref(),
+            2 => self.part_two.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, Error> {
+        let mut answers = Self::default();
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || Error::Parser(format!("line {}: {line}", index + 1));
+
+            let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+            let value = parse_value(value.trim()).ok_or_else(invalid)?;
+
+            match key.trim() {
+                "part_one" => answers.part_one = Some(value),
+                "part_two" => answers.part_two = Some(value),
+                // unknown keys are left alone, so the file can hold notes.
+                _ => {}
+            }
+        }
+
+        Ok(answers)
+    }
+
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        [("part_one", &self.part_one), ("part_two", &self.part_two)]
+            .iter()
+            .filter_map(|(key, value)| {
+                value
+                    .as_ref()
+                    .map(|value| format!("{key} = {}\n", quote(value)))
+            })
+            .collect()
+    }
+}
+
+#[must_use]
+pub fn get_path(year: Year, set: &str, day: Day) -> PathBuf {
+    let folder = if set == DEFAULT_INPUT_SET {
+        ANSWERS_FOLDER.to_string()
+    } else {
+        format!("{ANSWERS_FOLDER}/{set}")
+    };
+
+    env::current_dir()
+        .unwrap()
+        .join(year.data_path(&folder, &format!("{day}.toml")))
+}
+
+/// Loads the answers of a day. A missing file yields no answers.
+pub fn load(year: Year, set: &str, day: Day) -> Result<Answers, Error> {
+    let path = get_path(year, set, day);
+
+    if !path.exists() {
+        return Ok(Answers::default());
+    }
+
+    Answers::parse(&fs::read_to_string(path)?)
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    // strip trailing comments of bare values, quoted values are parsed char by char.
+    let Some(quoted) = value.strip_prefix('"') else {
+        let value = value.split('#').next()?.trim();
+        return value
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '-' || c == '_')
+            .then(|| value.replace('_', ""))
+            .filter(|x| !x.is_empty());
+    };
+
+    let mut result = String::new();
+    let mut chars = quoted.chars();
+
+    loop {
+        match chars.next()? {
+            '"' => break,
+            '\\' => result.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '"' => '"',
+                '\\' => '\\',
+                _ => return None,
+            }),
+            c => result.push(c),
+        }
+    }
+
+    let rest = chars.as_str().trim();
+    (rest.is_empty() || rest.starts_with('#')).then_some(result)
+}
+
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn parses_answers() {
+        let answers = Answers::parse(
+            "# day 5\npart_one = \"35\"\n\npart_two = 46 # after refactoring\nnotes = \"slow\"\n",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1).unwrap(), "35");
+        assert_eq!(answers.get(2).unwrap(), "46");
+        assert_eq!(answers.get(3), None);
+    }
+
+    #[test]
+    fn parses_missing_parts() {
+        let answers = Answers::parse("part_one = 1_000").unwrap();
+        assert_eq!(answers.get(1).unwrap(), "1000");
+        assert_eq!(answers.get(2), None);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(Answers::parse("part_one").is_err());
+        assert!(Answers::parse("part_one = \"35").is_err());
+        assert!(Answers::parse("part_one = abc").is_err());
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let answers = Answers {
+            part_one: Some("#..\n\"x\"\t\\".into()),
+            part_two: None,
+        };
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+    }
+}
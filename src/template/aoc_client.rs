@@ -0,0 +1,614 @@
+/// Built-in client for the Advent of Code website. Fetches inputs and puzzle descriptions and submits answers.
+/// The session cookie is read from the `AOC_SESSION` variable or from an `.adventofcode.session` file in the home directory.
+/// The base URL can be changed with `AOC_BASE_URL`, e.g. to run against a local stand-in server.
+/// Sessions of further accounts are read from an `.adventofcode.sessions` file in the home directory, one `name = cookie` per line.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{
+    answers::{self, Answers},
+    input::{self, DEFAULT_INPUT_SET},
+    integrity::{self, Problem},
+    verdict::Verdict,
+    year::{Year, YEAR_ENV},
+};
+use crate::Day;
+
+const BASE_URL_ENV: &str = "AOC_BASE_URL";
+const SESSION_ENV: &str = "AOC_SESSION";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE: &str = ".adventofcode.session";
+const SESSIONS_FILE: &str = ".adventofcode.sessions";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotSet,
+    /// The sessions file has a malformed line.
+    InvalidSessions(String),
+    /// No session is configured for an input set.
+    InputSetNotConfigured(String),
+    /// The downloaded input is not a puzzle input, e.g. an error page.
+    InvalidInput(Problem),
+    /// The server answered with an error status, e.g. `404` for a puzzle that is not unlocked yet.
+    BadStatus(u16, String),
+    Network(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "session cookie not found. Set {SESSION_ENV} or create a \"~/{SESSION_FILE}\" file."
+            ),
+            AocClientError::YearNotSet => {
+                write!(
+                    f,
+                    "{YEAR_ENV} is not set. Configure it in .cargo/config.toml."
+                )
+            }
+            AocClientError::InvalidSessions(line) => {
+                write!(f, "malformed line in \"~/{SESSIONS_FILE}\": {line}")
+            }
+            AocClientError::InputSetNotConfigured(set) => write!(
+                f,
+                "no session configured for input set \"{set}\". Add it to \"~/{SESSIONS_FILE}\"."
+            ),
+            AocClientError::InvalidInput(problem) => {
+                write!(f, "the downloaded input is {problem}, it was not written.")
+            }
+            AocClientError::BadStatus(status, body) => {
+                write!(f, "server responded with status {status}: {}", body.trim())
+            }
+            AocClientError::Network(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "could not write output files to file system: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Creates a client for a year from the environment and the session file.
+    pub fn from_env(year: Year) -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Self::from_env_with_session(year, &session)
+    }
+
+    /// Creates a client for a year and the session of another account.
+    pub fn from_env_with_session(year: Year, session: &str) -> Result<Self, AocClientError> {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let year = year.number().ok_or(AocClientError::YearNotSet)?;
+        Ok(Self::new(&base_url, session, year))
+    }
+
+    pub fn get_input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("/{}/day/{}/input", self.year, day.into_inner()))
+    }
+
+    /// Fetches the puzzle description of a day, converted to markdown.
+    pub fn get_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        Ok(html_to_markdown(&self.get_puzzle_page(day)?))
+    }
+
+    /// Fetches the answers the account has solved a day with.
+    pub fn get_accepted_answers(&self, day: Day) -> Result<Answers, AocClientError> {
+        Ok(parse_accepted_answers(&self.get_puzzle_page(day)?))
+    }
+
+    /// Fetches the JSON of a private leaderboard. The website asks to not fetch it more than once every 15 minutes.
+    pub fn get_leaderboard(&self, id: u64) -> Result<String, AocClientError> {
+        self.get(&format!(
+            "/{}/leaderboard/private/view/{id}.json",
+            self.year
+        ))
+    }
+
+    fn get_puzzle_page(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("/{}/day/{}", self.year, day.into_inner()))
+    }
+
+    /// Submits an answer and returns the verdict of the response.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<Verdict, AocClientError> {
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url,
+            self.year,
+            day.into_inner()
+        );
+
+        let response = self
+            .request(ureq::post(&url))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        Ok(Verdict::parse(&html_to_markdown(&read_response(response)?)))
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let url = format!("{}{path}", self.base_url);
+        read_response(self.request(ureq::get(&url)).call())
+    }
+
+    fn request(&self, request: ureq::Request) -> ureq::Request {
+        request
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+    }
+}
+
+/// Downloads the input and the puzzle description of a day to the data folder of its year.
+/// An existing input is only replaced if it is invalid, e.g. empty, or if `force` is set. The puzzle description is
+/// always replaced, it grows once the first part is solved.
+pub fn download(year: Year, day: Day, force: bool) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(year)?;
+
+    let input_path = input::get_path(year, DEFAULT_INPUT_SET, day);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    if should_write_input(&input_path, force) {
+        write_input(&input_path, &client.get_input(day)?)?;
+
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+    }
+
+    write_file(&puzzle_path, &client.get_puzzle(day)?)?;
+
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Downloads the input of another account to its input set, together with the answers the account has solved the day with.
+pub fn download_set(
+    year: Year,
+    day: Day,
+    set: &str,
+    session: &str,
+    force: bool,
+) -> Result<(), AocClientError> {
+    let client = AocClient::from_env_with_session(year, session)?;
+
+    let input_path = input::get_path(year, set, day);
+
+    if should_write_input(&input_path, force) {
+        write_input(&input_path, &client.get_input(day)?)?;
+
+        println!(
+            "🎄 Successfully wrote input of set \"{set}\" to \"{}\".",
+            input_path.display()
+        );
+    }
+
+    let answers = client.get_accepted_answers(day)?;
+
+    if answers != Answers::default() {
+        let answers_path = answers::get_path(year, set, day);
+        write_file(&answers_path, &answers.to_toml())?;
+
+        let cwd = env::current_dir()?;
+
+        println!(
+            "🎄 Successfully wrote answers of set \"{set}\" to \"{}\".",
+            answers_path
+                .strip_prefix(&cwd)
+                .unwrap_or(&answers_path)
+                .display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Prints the puzzle description of a day.
+pub fn read(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(year)?;
+    println!("{}", client.get_puzzle(day)?);
+    Ok(())
+}
+
+/// Fetches the JSON of a private leaderboard of a year.
+pub fn get_leaderboard(year: Year, id: u64) -> Result<String, AocClientError> {
+    AocClient::from_env(year)?.get_leaderboard(id)
+}
+
+pub fn submit(year: Year, day: Day, part: u8, result: &str) -> Result<Verdict, AocClientError> {
+    AocClient::from_env(year)?.submit(day, part, result)
+}
+
+#[must_use]
+pub fn get_puzzle_path(year: Year, day: Day) -> PathBuf {
+    year.data_path("puzzles", &format!("{day}.md"))
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session);
+    }
+
+    let session = fs::read_to_string(get_home()?.join(SESSION_FILE)).ok()?;
+
+    Some(session.trim().to_string()).filter(|x| !x.is_empty())
+}
+
+/// Reads the named sessions of further accounts. A missing file yields no sessions.
+/// The configured session is always available as the `default` set.
+pub fn get_sessions() -> Result<Vec<(String, String)>, AocClientError> {
+    let Some(contents) =
+        get_home().and_then(|home| fs::read_to_string(home.join(SESSIONS_FILE)).ok())
+    else {
+        return Ok(vec![]);
+    };
+
+    parse_sessions(&contents)
+}
+
+fn parse_sessions(contents: &str) -> Result<Vec<(String, String)>, AocClientError> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            // only the name is shown in errors, the session cookie is a secret.
+            let (name, session) = line
+                .split_once('=')
+                .ok_or_else(|| AocClientError::InvalidSessions("missing \"=\"".into()))?;
+            let (name, session) = (name.trim(), session.trim());
+
+            if !input::is_valid_set_name(name) || name == DEFAULT_INPUT_SET || session.is_empty() {
+                return Err(AocClientError::InvalidSessions(format!("\"{name}\"")));
+            }
+
+            Ok((name.to_string(), session.to_string()))
+        })
+        .collect()
+}
+
+fn get_home() -> Option<PathBuf> {
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    Some(PathBuf::from(home))
+}
+
+/// Checks whether an input may be written. Valid inputs are kept unless `force` is set.
+fn should_write_input(path: &Path, force: bool) -> bool {
+    let Ok(existing) = fs::read_to_string(path) else {
+        return true;
+    };
+
+    if force || integrity::check(&existing).is_err() {
+        return true;
+    }
+
+    println!(
+        "Kept existing input \"{}\". Pass --force to download it again.",
+        path.display()
+    );
+    false
+}
+
+/// Writes a downloaded input after checking its contents, and records its checksum.
+fn write_input(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    integrity::check(contents).map_err(AocClientError::InvalidInput)?;
+    write_file(path, contents)?;
+    integrity::record_checksum(path, contents)?;
+    Ok(())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocClientError::Network(e.to_string())),
+        Err(ureq::Error::Status(status, response)) => Err(AocClientError::BadStatus(
+            status,
+            response.into_string().unwrap_or_default(),
+        )),
+        Err(e) => Err(AocClientError::Network(e.to_string())),
+    }
+}
+
+/// Reads the accepted answers from a puzzle page, which shows them below the description of each solved part.
+fn parse_accepted_answers(html: &str) -> Answers {
+    const PREFIX: &str = "Your puzzle answer was <code>";
+
+    let mut found = html.match_indices(PREFIX).filter_map(|(start, _)| {
+        let rest = &html[start + PREFIX.len()..];
+        let end = rest.find("</code>")?;
+        Some(decode_entities(&rest[..end]))
+    });
+
+    Answers {
+        part_one: found.next(),
+        part_two: found.next(),
+    }
+}
+
+/// Converts the `<article>` elements of a page to markdown. Code blocks are kept verbatim.
+fn html_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let body = &rest[start..];
+        let Some(open_end) = body.find('>') else {
+            break;
+        };
+        let end = body.find("</article>").unwrap_or(body.len());
+        markdown.push_str(&fragment_to_markdown(&body[open_end + 1..end]));
+        rest = &body[end..];
+    }
+
+    let mut result = String::new();
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        if line.trim() == "```" {
+            in_code = !in_code;
+        }
+
+        // code blocks are kept verbatim, elsewhere blank lines left behind by nested block elements are collapsed.
+        let line = if in_code { line } else { line.trim() };
+
+        if !in_code && line.is_empty() && (result.is_empty() || result.ends_with("\n\n")) {
+            continue;
+        }
+
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    result.trim_end().to_string() + "\n"
+}
+
+fn fragment_to_markdown(fragment: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut rest = fragment;
+
+    while !rest.is_empty() {
+        if let Some(tag_body) = rest.strip_prefix('<') {
+            let Some(end) = tag_body.find('>') else {
+                break;
+            };
+            let tag = &tag_body[..end];
+            rest = &tag_body[end + 1..];
+
+            let is_closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default();
+
+            match (name, is_closing) {
+                ("h2", false) => out.push_str("\n## "),
+                ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+                ("pre", false) => {
+                    in_pre = true;
+                    out.push_str("\n```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                }
+                ("code", _) if !in_pre => out.push('`'),
+                ("em", _) if !in_pre => out.push('*'),
+                ("li", false) => out.push_str("- "),
+                ("li", true) | ("br", _) => out.push('\n'),
+                _ => {}
+            }
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = decode_entities(&rest[..end]);
+            if in_pre {
+                out.push_str(&text);
+            } else {
+                // outside of code blocks, whitespace is not significant in html.
+                let mut last_was_space = false;
+                for c in text.chars() {
+                    if !c.is_whitespace() {
+                        out.push(c);
+                    } else if !last_was_space {
+                        out.push(' ');
+                    }
+                    last_was_space = c.is_whitespace();
+                }
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    out
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        html_to_markdown, parse_accepted_answers, parse_sessions, AocClient, AocClientError,
+    };
+    use crate::day;
+    use crate::template::verdict::Verdict;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    /// Serves a single request with a canned response and reports the raw request it received.
+    fn serve_once(status: u16, body: &'static str) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut payload = vec![0; content_length];
+            reader.read_exact(&mut payload).unwrap();
+            request.push_str(&String::from_utf8(payload).unwrap());
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (base_url, rx) = serve_once(200, "1abc2\n");
+        let client = AocClient::new(&base_url, "secret\n", 2023);
+
+        assert_eq!(client.get_input(day!(1)).unwrap(), "1abc2\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, rx) = serve_once(
+            200,
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret", 2023);
+
+        let verdict = client.submit(day!(5), 2, "46").unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/5/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=46"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, _rx) = serve_once(404, "Not found");
+        let client = AocClient::new(&base_url, "secret", 2023);
+
+        assert!(matches!(
+            client.get_input(day!(25)),
+            Err(AocClientError::BadStatus(404, _))
+        ));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let html = r#"<html><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>For example:</p>
+<pre><code>1abc2
+pqr<em>3</em>stu8vwx
+</code></pre>
+<p>The answer is <code><em>142</em></code> &amp; more.</p>
+<ul><li>one</li><li>two</li></ul>
+</article></main></html>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Trebuchet?! ---\n\nFor example:\n\n```\n1abc2\npqr3stu8vwx\n```\n\nThe answer is `*142*` & more.\n\n- one\n- two\n"
+        );
+    }
+
+    #[test]
+    fn parses_accepted_answers() {
+        let html = r#"<article><p>...</p></article><p>Your puzzle answer was <code>54634</code>.</p>
+<article><p>...</p></article><p>Your puzzle answer was <code>a&amp;b</code>.</p>"#;
+
+        let answers = parse_accepted_answers(html);
+        assert_eq!(answers.part_one.as_deref(), Some("54634"));
+        assert_eq!(answers.part_two.as_deref(), Some("a&b"));
+        assert_eq!(parse_accepted_answers("<article></article>").part_one, None);
+    }
+
+    #[test]
+    fn parses_sessions() {
+        let sessions = parse_sessions("# team\nalice = 53616c\n\nbob=7465645f\n").unwrap();
+        assert_eq!(
+            sessions,
+            vec![
+                ("alice".to_string(), "53616c".to_string()),
+                ("bob".to_string(), "7465645f".to_string())
+            ]
+        );
+
+        assert!(parse_sessions("alice 53616c").is_err());
+        assert!(parse_sessions("../alice = 53616c").is_err());
+        assert!(parse_sessions("default = 53616c").is_err());
+    }
+}
@@ -0,0 +1,215 @@
+/// Settings that control how long `--time` benches a solution part.
+/// Every setting can be passed on the command-line or configured through an `AOC_BENCH_*` environment variable,
+/// e.g. in the `[env]` section of `.cargo/config.toml`. Command-line values take precedence.
+use std::{cmp, env, str::FromStr, time::Duration};
+
+const TARGET_TIME_ARG: &str = "--target-time";
+const MIN_SAMPLES_ARG: &str = "--min-samples";
+const MAX_SAMPLES_ARG: &str = "--max-samples";
+const WARMUP_ARG: &str = "--warmup";
+
+const TARGET_TIME_ENV: &str = "AOC_BENCH_TARGET_MS";
+const MIN_SAMPLES_ENV: &str = "AOC_BENCH_MIN_SAMPLES";
+const MAX_SAMPLES_ENV: &str = "AOC_BENCH_MAX_SAMPLES";
+const WARMUP_ENV: &str = "AOC_BENCH_WARMUP";
+
+/// Bench settings that were explicitly set on the command-line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Approximate time budget per part, in milliseconds.
+    pub target_millis: Option<u64>,
+    pub min_samples: Option<u128>,
+    pub max_samples: Option<u128>,
+    /// Number of unrecorded runs before sampling starts.
+    pub warmup: Option<u128>,
+}
+
+impl BenchOptions {
+    /// Reads the options from command-line arguments, e.g. `--min-samples 5`.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        Ok(Self {
+            target_millis: parse_arg(args, TARGET_TIME_ARG)?,
+            min_samples: parse_arg(args, MIN_SAMPLES_ARG)?,
+            max_samples: parse_arg(args, MAX_SAMPLES_ARG)?,
+            warmup: parse_arg(args, WARMUP_ARG)?,
+        })
+    }
+
+    /// Converts the options back to command-line arguments, so they can be mirrored to child invocations.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        let mut push = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                args.push(name.to_string());
+                args.push(value);
+            }
+        };
+
+        push(TARGET_TIME_ARG, self.target_millis.map(|x| x.to_string()));
+        push(MIN_SAMPLES_ARG, self.min_samples.map(|x| x.to_string()));
+        push(MAX_SAMPLES_ARG, self.max_samples.map(|x| x.to_string()));
+        push(WARMUP_ARG, self.warmup.map(|x| x.to_string()));
+
+        args
+    }
+
+    /// Fills unset options from the environment, then from defaults.
+    #[must_use]
+    pub fn resolve(&self) -> BenchConfig {
+        let default = BenchConfig::default();
+
+        let target_millis = self.target_millis.or_else(|| parse_env(TARGET_TIME_ENV));
+        // at least one sample is needed for a timing.
+        let min_samples = self
+            .min_samples
+            .or_else(|| parse_env(MIN_SAMPLES_ENV))
+            .map_or(default.min_samples, |x| cmp::max(x, 1));
+        let max_samples = self
+            .max_samples
+            .or_else(|| parse_env(MAX_SAMPLES_ENV))
+            .unwrap_or(default.max_samples);
+
+        BenchConfig {
+            target: target_millis.map_or(default.target, Duration::from_millis),
+            min_samples,
+            // a minimum above the maximum wins, the user asked for at least that many samples.
+            max_samples: cmp::max(max_samples, min_samples),
+            warmup: self.warmup.or_else(|| parse_env(WARMUP_ENV)),
+        }
+    }
+}
+
+/// Resolved bench settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub target: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Number of warmup runs. If unset, a tenth of the sample count is used.
+    pub warmup: Option<u128>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            target: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            warmup: None,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Number of samples to take for a solution whose first execution took `base_time`.
+    #[must_use]
+    pub fn sample_count(&self, base_time: &Duration) -> u128 {
+        (self.target.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+
+    #[must_use]
+    pub fn warmup_count(&self, samples: u128) -> u128 {
+        self.warmup.unwrap_or_else(|| cmp::max(samples / 10, 1))
+    }
+}
+
+fn parse_arg<T: FromStr>(args: &[String], name: &str) -> Result<Option<T>, String> {
+    let Some(index) = args.iter().position(|x| x == name) else {
+        return Ok(None);
+    };
+
+    args.get(index + 1)
+        .and_then(|x| x.parse().ok())
+        .map(Some)
+        .ok_or_else(|| format!("expecting a non-negative number after {name}"))
+}
+
+fn parse_env<T: FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok()?.parse().ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchConfig, BenchOptions};
+    use crate::template::test_helpers::args;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_args() {
+        let options =
+            BenchOptions::from_args(&args("--time --target-time 50 --max-samples 20")).unwrap();
+        assert_eq!(options.target_millis, Some(50));
+        assert_eq!(options.max_samples, Some(20));
+        assert_eq!(options.min_samples, None);
+        assert_eq!(options.warmup, None);
+    }
+
+    #[test]
+    fn rejects_malformed_args() {
+        assert!(BenchOptions::from_args(&args("--warmup")).is_err());
+        assert!(BenchOptions::from_args(&args("--warmup abc")).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        let options = BenchOptions {
+            target_millis: Some(100),
+            min_samples: Some(3),
+            max_samples: None,
+            warmup: Some(0),
+        };
+        assert_eq!(
+            BenchOptions::from_args(&options.to_args()).unwrap(),
+            options
+        );
+    }
+
+    #[test]
+    fn resolves_sample_count() {
+        let config = BenchOptions {
+            target_millis: Some(10),
+            min_samples: Some(5),
+            max_samples: Some(50),
+            warmup: None,
+        }
+        .resolve();
+
+        assert_eq!(config.sample_count(&Duration::from_millis(1)), 10);
+        assert_eq!(config.sample_count(&Duration::from_secs(1)), 5);
+        assert_eq!(config.sample_count(&Duration::from_nanos(1)), 50);
+        assert_eq!(config.warmup_count(50), 5);
+    }
+
+    #[test]
+    fn min_samples_win_over_max_samples() {
+        let config = BenchOptions {
+            target_millis: None,
+            min_samples: Some(100),
+            max_samples: Some(10),
+            warmup: Some(0),
+        }
+        .resolve();
+
+        assert_eq!(config.max_samples, 100);
+        assert_eq!(config.warmup_count(100), 0);
+        assert_eq!(BenchConfig::default().min_samples, 10);
+    }
+
+    #[test]
+    fn resolves_zero_samples() {
+        let config = BenchOptions {
+            target_millis: Some(10),
+            min_samples: Some(0),
+            max_samples: Some(0),
+            warmup: None,
+        }
+        .resolve();
+
+        assert_eq!(config.min_samples, 1);
+        assert_eq!(config.max_samples, 1);
+        assert_eq!(config.sample_count(&Duration::from_millis(1)), 1);
+    }
+}
@@ -0,0 +1,403 @@
+/// Module that keeps a history of benchmark results in `data/benchmarks/` (or the folder of the year), so runs can be compared over time.
+/// Every run is stored with the git commit it was measured on and a timestamp.
+use std::{
+    env, fs, io,
+    path::PathBuf,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    json::{object, OrderedJson},
+    readme_benchmarks::Timings,
+    runner::PARSE_LABEL,
+    year::Year,
+};
+use crate::Day;
+
+/// Version of the history file format. Bump this when making incompatible changes.
+const VERSION: f64 = 1.0;
+
+const HISTORY_FOLDER: &str = "benchmarks";
+const HISTORY_FILE: &str = "history.json";
+
+const THRESHOLD_ENV: &str = "AOC_BENCH_REGRESSION_THRESHOLD";
+
+/// Default change in percent above which a slowdown is flagged as regression.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// A single measurement. Part `0` denotes the shared parse step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: Day,
+    pub part: u8,
+    pub nanos: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub commit: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+/// The change of a single measurement relative to its baseline.
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+    pub baseline_commit: String,
+}
+
+impl Comparison {
+    #[must_use]
+    pub fn change_percent(&self) -> f64 {
+        (self.current_nanos - self.baseline_nanos) / self.baseline_nanos * 100.0
+    }
+
+    #[must_use]
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold
+    }
+}
+
+impl Run {
+    /// Creates a run for the current commit and time.
+    #[must_use]
+    pub fn from_timings(timings: &[Timings]) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            commit: current_commit().unwrap_or_else(|| "unknown".into()),
+            timestamp,
+            entries: entries_from_timings(timings),
+        }
+    }
+}
+
+impl History {
+    /// Finds the most recent measurement of a part.
+    #[must_use]
+    pub fn baseline(&self, day: Day, part: u8) -> Option<(&Run, &Entry)> {
+        self.runs.iter().rev().find_map(|run| {
+            run.entries
+                .iter()
+                .find(|e| e.day == day && e.part == part)
+                .map(|e| (run, e))
+        })
+    }
+
+    /// Compares a run against the most recent measurement of each of its parts.
+    #[must_use]
+    pub fn compare(&self, run: &Run) -> Vec<Comparison> {
+        run.entries
+            .iter()
+            .filter_map(|entry| {
+                let (baseline_run, baseline) = self.baseline(entry.day, entry.part)?;
+                if baseline.nanos <= 0.0 {
+                    return None;
+                }
+                Some(Comparison {
+                    day: entry.day,
+                    part: entry.part,
+                    baseline_nanos: baseline.nanos,
+                    current_nanos: entry.nanos,
+                    baseline_commit: baseline_run.commit.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn to_json(&self) -> OrderedJson {
+        let runs: Vec<OrderedJson> = self
+            .runs
+            .iter()
+            .map(|run| {
+                let entries: Vec<OrderedJson> = run
+                    .entries
+                    .iter()
+                    .map(|e| {
+                        object(vec![
+                            ("day", f64::from(e.day.into_inner()).into()),
+                            ("part", f64::from(e.part).into()),
+                            ("nanos", e.nanos.into()),
+                        ])
+                    })
+                    .collect();
+
+                #[allow(clippy::cast_precision_loss)]
+                object(vec![
+                    ("commit", run.commit.clone().into()),
+                    ("timestamp", (run.timestamp as f64).into()),
+                    ("entries", entries.into()),
+                ])
+            })
+            .collect();
+
+        object(vec![("version", VERSION.into()), ("runs", runs.into())])
+    }
+
+    fn from_json(value: &JsonValue) -> Result<Self, Error> {
+        let invalid = || Error::Parser("Malformed benchmark history.".into());
+
+        let version: &f64 = value["version"].get().ok_or_else(invalid)?;
+        if *version > VERSION {
+            return Err(Error::Parser(format!(
+                "Benchmark history has version {version}, only versions up to {VERSION} are supported."
+            )));
+        }
+
+        let runs: &Vec<JsonValue> = value["runs"].get().ok_or_else(invalid)?;
+
+        let runs = runs
+            .iter()
+            .map(|run| {
+                let commit: &String = run["commit"].get()?;
+                let timestamp: &f64 = run["timestamp"].get()?;
+                let entries: &Vec<JsonValue> = run["entries"].get()?;
+
+                let entries = entries
+                    .iter()
+                    .map(|e| {
+                        let day: &f64 = e["day"].get()?;
+                        let part: &f64 = e["part"].get()?;
+                        let nanos: &f64 = e["nanos"].get()?;
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        Some(Entry {
+                            day: Day::new(*day as u8)?,
+                            part: *part as u8,
+                            nanos: *nanos,
+                        })
+                    })
+                    .collect::<Option<Vec<Entry>>>()?;
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Some(Run {
+                    commit: commit.clone(),
+                    timestamp: *timestamp as u64,
+                    entries,
+                })
+            })
+            .collect::<Option<Vec<Run>>>()
+            .ok_or_else(invalid)?;
+
+        Ok(Self { runs })
+    }
+}
+
+fn get_path(year: Year) -> PathBuf {
+    year.data_path(HISTORY_FOLDER, HISTORY_FILE)
+}
+
+/// Loads the stored history of a year. A missing file yields an empty history.
+pub fn load(year: Year) -> Result<History, Error> {
+    let path = get_path(year);
+
+    if !path.exists() {
+        return Ok(History::default());
+    }
+
+    let contents = fs::read_to_string(path)?;
+    let value: JsonValue = contents
+        .parse()
+        .map_err(|e| Error::Parser(format!("{e}")))?;
+
+    History::from_json(&value)
+}
+
+pub fn save(year: Year, history: &History) -> Result<(), Error> {
+    let path = get_path(year);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let json = history
+        .to_json()
+        .format()
+        .map_err(|e| Error::Parser(format!("{e}")))?;
+
+    fs::write(path, json + "\n")?;
+    Ok(())
+}
+
+/// Reads the regression threshold from the environment, falling back to [`DEFAULT_THRESHOLD`].
+#[must_use]
+pub fn threshold_from_env() -> f64 {
+    env::var(THRESHOLD_ENV)
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(DEFAULT_THRESHOLD)
+}
+
+/// Formats a comparison for console output, e.g. `Day 01 Part 1: 1.0µs → 1.2µs (+20.0%)`.
+#[must_use]
+pub fn format_comparison(comparison: &Comparison, threshold: f64) -> String {
+    let part = match comparison.part {
+        0 => PARSE_LABEL.to_string(),
+        n => format!("Part {n}"),
+    };
+
+    let marker = if comparison.is_regression(threshold) {
+        " ⚠ regression"
+    } else {
+        ""
+    };
+
+    format!(
+        "Day {} {part}: {} → {} ({:+.1}%){marker}",
+        comparison.day,
+        format_nanos(comparison.baseline_nanos),
+        format_nanos(comparison.current_nanos),
+        comparison.change_percent(),
+    )
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!(
+        "{:.1?}",
+        std::time::Duration::from_nanos(nanos.round() as u64)
+    )
+}
+
+fn entries_from_timings(timings: &[Timings]) -> Vec<Entry> {
+    timings
+        .iter()
+        .flat_map(|t| {
+            [(0, t.parse_stats), (1, t.part_1_stats), (2, t.part_2_stats)]
+                .into_iter()
+                .filter_map(move |(part, stats)| {
+                    #[allow(clippy::cast_precision_loss)]
+                    stats.map(|s| Entry {
+                        day: t.day,
+                        part,
+                        nanos: s.mean.as_nanos() as f64,
+                    })
+                })
+        })
+        .collect()
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_comparison, Entry, History, Run};
+    use crate::day;
+
+    fn run(commit: &str, entries: &[(u8, f64)]) -> Run {
+        Run {
+            commit: commit.into(),
+            timestamp: 1_700_000_000,
+            entries: entries
+                .iter()
+                .map(|(part, nanos)| Entry {
+                    day: day!(1),
+                    part: *part,
+                    nanos: *nanos,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn roundtrips_history() {
+        let history = History {
+            runs: vec![run("abc123", &[(0, 10.0), (1, 1500.0)]), run("def456", &[])],
+        };
+        let json = history.to_json().format().unwrap();
+        let parsed = History::from_json(&json.parse().unwrap()).unwrap();
+        assert_eq!(parsed, history);
+    }
+
+    #[test]
+    fn writes_keys_in_order() {
+        let history = History {
+            runs: vec![run("abc123", &[(1, 1500.0), (2, 20.0)])],
+        };
+        let json = history.to_json().format().unwrap();
+
+        // a versioned file must not change between saves of the same history.
+        assert_eq!(json, history.to_json().format().unwrap());
+        assert!(json.starts_with("{\n  \"version\": 1,\n  \"runs\": ["));
+        assert!(json.contains("\"day\": 1,\n          \"part\": 1,\n          \"nanos\": 1500"));
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let value = r#"{"version": 99, "runs": []}"#.parse().unwrap();
+        assert!(History::from_json(&value).is_err());
+    }
+
+    #[test]
+    fn compares_against_latest_baseline() {
+        let history = History {
+            runs: vec![
+                run("old", &[(1, 100.0), (2, 100.0)]),
+                run("new", &[(1, 200.0)]),
+            ],
+        };
+
+        let comparisons = history.compare(&run("current", &[(1, 250.0), (2, 105.0), (0, 5.0)]));
+        assert_eq!(comparisons.len(), 2);
+
+        assert_eq!(comparisons[0].baseline_commit, "new");
+        assert_eq!(comparisons[0].change_percent(), 25.0);
+        assert!(comparisons[0].is_regression(10.0));
+
+        assert_eq!(comparisons[1].baseline_commit, "old");
+        assert!(!comparisons[1].is_regression(10.0));
+    }
+
+    #[test]
+    fn formats_comparison() {
+        let history = History {
+            runs: vec![run("old", &[(1, 1000.0)])],
+        };
+        let comparisons = history.compare(&run("current", &[(1, 1200.0)]));
+        assert_eq!(
+            format_comparison(&comparisons[0], 10.0),
+            "Day 01 Part 1: 1.0µs → 1.2µs (+20.0%) ⚠ regression"
+        );
+        assert_eq!(
+            format_comparison(&comparisons[0], 50.0),
+            "Day 01 Part 1: 1.0µs → 1.2µs (+20.0%)"
+        );
+    }
+}
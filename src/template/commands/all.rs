@@ -1,48 +1,505 @@
-use std::io;
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
 use crate::template::{
+    bench_config::BenchOptions,
+    benchmark_history,
+    input::{self, InputSets, InputSource, DEFAULT_INPUT_SET},
+    integrity,
     readme_benchmarks::{self, Timings},
+    report::{PartReport, Status},
+    timeout::TimeoutOptions,
+    year::Year,
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+const JOBS_ENV: &str = "AOC_JOBS";
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+/// How the solution binaries are invoked.
+struct RunOptions {
+    is_timed: bool,
+    bench: BenchOptions,
+    timeouts: TimeoutOptions,
+}
+
+/// Output of a day, buffered so days that run in parallel are still printed in order.
+#[derive(Debug, Default)]
+struct Output(Vec<Chunk>);
+
+#[derive(Debug, PartialEq, Eq)]
+enum Chunk {
+    Stdout(String),
+    Stderr(String),
+}
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+impl Output {
+    fn print(&mut self, text: String) {
+        self.0.push(Chunk::Stdout(text));
+    }
+
+    fn println(&mut self, line: impl Display) {
+        self.print(format!("{line}\n"));
+    }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    fn eprintln(&mut self, line: impl Display) {
+        self.0.push(Chunk::Stderr(format!("{line}\n")));
+    }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+    fn write(&self) {
+        for chunk in &self.0 {
+            match chunk {
+                Chunk::Stdout(text) => print!("{text}"),
+                Chunk::Stderr(text) => eprint!("{text}"),
+            }
         }
+
+        let _ = io::stdout().flush();
+    }
+}
+
+/// The result of running all input sets of a day.
+struct DayOutcome {
+    day: Day,
+    output: Output,
+    /// The reports of every input set, `None` for sets without a usable input. `None` if the day did not run.
+    reports_by_set: Option<Vec<Option<Vec<PartReport>>>>,
+    timings: Option<Timings>,
+    failed_build: bool,
+    invalid_input: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    is_timed: bool,
+    bench: BenchOptions,
+    compare: bool,
+    threshold: Option<f64>,
+    alloc: bool,
+    timeouts: TimeoutOptions,
+    inputs: Option<InputSets>,
+    jobs: Option<usize>,
+) {
+    let options = RunOptions {
+        is_timed,
+        bench,
+        timeouts: timeouts.resolve(),
+    };
+    let jobs = resolve_jobs(jobs, is_timed);
+
+    let mut timings: Vec<Timings> = vec![];
+    let mut timed_out: Vec<Day> = vec![];
+    let mut panicked: Vec<Day> = vec![];
+    let mut invalid_inputs: Vec<Day> = vec![];
+    let mut failed_builds: Vec<Day> = vec![];
+    let mut answer_rows: Vec<AnswerRow> = vec![];
+
+    let sets = match &inputs {
+        Some(inputs) => inputs.resolve(&input::list_sets(year)),
+        None => vec![DEFAULT_INPUT_SET.to_string()],
+    };
+
+    if year.is_namespaced() {
+        println!("{ANSI_BOLD}🎄 {year}{ANSI_RESET}\n");
+    }
+
+    // days that have not been scaffolded yet are not built.
+    let solved: Vec<Day> = all_days()
+        .filter(|day| Path::new(&year.bin_path(*day)).exists())
+        .collect();
+
+    let executables = child_commands::build(year, &solved, is_release, alloc).unwrap_or_else(|e| {
+        eprintln!("Failed to build solutions: {e:?}");
+        std::process::exit(1);
     });
 
+    if is_timed && jobs > 1 {
+        println!("⚠ Benchmarking {jobs} days in parallel, timings are less reliable.\n");
+    }
+
+    let days: Vec<Day> = all_days().collect();
+
+    run_in_order(
+        days.len(),
+        jobs,
+        |index| {
+            let day = days[index];
+            let executable = executables.get(&day).map(PathBuf::as_path);
+            run_day(year, day, executable, &sets, inputs.is_some(), &options)
+        },
+        |outcome| {
+            let day = outcome.day;
+
+            if day > 1 {
+                println!();
+            }
+
+            outcome.output.write();
+
+            let reports = outcome.reports_by_set.iter().flatten().flatten().flatten();
+
+            if reports.clone().any(|r| r.status == Status::TimedOut) {
+                timed_out.push(day);
+            }
+
+            if reports
+                .clone()
+                .any(|r| matches!(r.status, Status::Panicked(_)))
+            {
+                panicked.push(day);
+            }
+
+            if outcome.invalid_input {
+                invalid_inputs.push(day);
+            }
+
+            if outcome.failed_build {
+                failed_builds.push(day);
+            }
+
+            timings.extend(outcome.timings);
+
+            if let Some(reports_by_set) = &outcome.reports_by_set {
+                answer_rows.extend(AnswerRow::from_reports(day, reports_by_set));
+            }
+        },
+    );
+
+    print_failed_days("Failed to build", &failed_builds);
+    print_failed_days("Timed out", &timed_out);
+    print_failed_days("Panicked", &panicked);
+    print_failed_days("Invalid input", &invalid_inputs);
+
+    if inputs.is_some() {
+        println!("\n{ANSI_BOLD}Answers by input set:{ANSI_RESET}");
+        print!("{}", format_answer_table(&sets, &answer_rows));
+    }
+
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
+        let run = benchmark_history::Run::from_timings(&timings);
+
+        if compare {
+            let threshold = threshold.unwrap_or_else(benchmark_history::threshold_from_env);
+            compare_with_history(year, &run, threshold);
+        }
+
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+            // the README only lists benchmarks of the configured year.
+            if !year.is_namespaced() {
+                match readme_benchmarks::update(timings, total_millis) {
+                    Ok(()) => println!("Successfully updated README with benchmarks."),
+                    Err(_) => {
+                        eprintln!("Failed to update readme with benchmarks.");
+                    }
+                }
+            }
+
+            match store_in_history(year, run) {
+                Ok(()) => println!("Successfully stored benchmarks in history."),
                 Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+                    eprintln!("Failed to store benchmarks in history.");
                 }
             }
         }
     }
 }
 
+/// Days run in parallel, one per core by default. Benchmarks run one day at a time, so days do not compete for the CPU.
+fn resolve_jobs(jobs: Option<usize>, is_timed: bool) -> usize {
+    let jobs = jobs.or_else(|| env::var(JOBS_ENV).ok()?.parse().ok());
+
+    match (jobs, is_timed) {
+        (Some(jobs), _) => jobs.max(1),
+        (None, true) => 1,
+        (None, false) => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+    }
+}
+
+/// Runs `count` tasks on up to `jobs` threads. Results are passed to `emit` in task order, as soon as all earlier tasks are done.
+fn run_in_order<T: Send>(
+    count: usize,
+    jobs: usize,
+    run: impl Fn(usize) -> T + Sync,
+    mut emit: impl FnMut(T),
+) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, count.max(1)) {
+            let (tx, next, run) = (tx.clone(), &next, &run);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+
+                if index >= count || tx.send((index, run(index))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        // results that arrive early wait for the tasks before them.
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+
+        for (index, result) in rx {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&expected) {
+                emit(result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+/// Runs the solution of a day with every input set. Everything the day prints is buffered in its outcome.
+fn run_day(
+    year: Year,
+    day: Day,
+    executable: Option<&Path>,
+    sets: &[String],
+    show_sets: bool,
+    options: &RunOptions,
+) -> DayOutcome {
+    let mut outcome = DayOutcome {
+        day,
+        output: Output::default(),
+        reports_by_set: None,
+        timings: None,
+        failed_build: false,
+        invalid_input: false,
+    };
+
+    let output = &mut outcome.output;
+    output.println(format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+    output.println("------");
+
+    if !Path::new(&year.bin_path(day)).exists() {
+        output.println("Not solved.");
+        return outcome;
+    }
+
+    // the compiler errors were printed by the build.
+    let Some(executable) = executable else {
+        output.println("✖ Failed to build.");
+        outcome.failed_build = true;
+        return outcome;
+    };
+
+    let mut reports_by_set: Vec<Option<Vec<PartReport>>> = vec![];
+
+    for set in sets {
+        if show_sets {
+            output.println(format!("{ANSI_ITALIC}{set}{ANSI_RESET}"));
+        }
+
+        let source = (set != DEFAULT_INPUT_SET).then(|| InputSource::Set(set.clone()));
+
+        // check inputs up front, so empty or broken inputs are reported instead of failing in the solution.
+        let Ok(contents) = fs::read_to_string(input::get_path(year, set, day)) else {
+            output.println("No input.");
+            reports_by_set.push(None);
+            continue;
+        };
+
+        if let Err(problem) = integrity::check(&contents) {
+            output.println(format!("✘ The input is {problem}."));
+            outcome.invalid_input = true;
+            reports_by_set.push(None);
+            continue;
+        }
+
+        let reports =
+            match child_commands::run_solution(executable, source.as_ref(), options, output) {
+                Ok(reports) => reports,
+                Err(e) => {
+                    output.eprintln(format!("Failed to run solution: {e:?}"));
+                    vec![]
+                }
+            };
+
+        // benchmarks are only kept for the puzzle input.
+        if set == DEFAULT_INPUT_SET && !reports.is_empty() {
+            outcome.timings = Some(child_commands::collect_timings(&reports, day));
+        }
+
+        reports_by_set.push(Some(reports));
+    }
+
+    outcome.reports_by_set = Some(reports_by_set);
+    outcome
+}
+
+fn print_failed_days(label: &str, days: &[Day]) {
+    if days.is_empty() {
+        return;
+    }
+
+    let days = days
+        .iter()
+        .map(|day| format!("Day {day}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    println!("\n{ANSI_BOLD}{label}:{ANSI_RESET} {days}");
+}
+
+fn compare_with_history(year: Year, run: &benchmark_history::Run, threshold: f64) {
+    let history = match benchmark_history::load(year) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to load benchmark history: {e:?}");
+            return;
+        }
+    };
+
+    let comparisons = history.compare(run);
+
+    println!("\n{ANSI_BOLD}Compared to baseline:{ANSI_RESET}");
+
+    if comparisons.is_empty() {
+        println!("No baseline found. Run `cargo time` to record one.");
+        return;
+    }
+
+    for comparison in &comparisons {
+        println!(
+            "{}",
+            benchmark_history::format_comparison(comparison, threshold)
+        );
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count();
+
+    if regressions > 0 {
+        println!("⚠ {regressions} regression(s) above {threshold}%.");
+    }
+}
+
+fn store_in_history(
+    year: Year,
+    run: benchmark_history::Run,
+) -> Result<(), benchmark_history::Error> {
+    let mut history = benchmark_history::load(year)?;
+    history.runs.push(run);
+    benchmark_history::save(year, &history)
+}
+
+/// The answers of a part for every input set.
+#[derive(Debug, PartialEq, Eq)]
+struct AnswerRow {
+    day: Day,
+    part: u8,
+    cells: Vec<String>,
+}
+
+impl AnswerRow {
+    fn from_reports(day: Day, reports_by_set: &[Option<Vec<PartReport>>]) -> Vec<Self> {
+        (1..=2)
+            .map(|part| AnswerRow {
+                day,
+                part,
+                cells: reports_by_set
+                    .iter()
+                    .map(|reports| {
+                        reports.as_ref().map_or("–".into(), |reports| {
+                            format_answer_cell(reports.iter().find(|r| r.part == part))
+                        })
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+fn format_answer_cell(report: Option<&PartReport>) -> String {
+    let Some(report) = report else {
+        return "✖".into();
+    };
+
+    match (&report.status, &report.answer) {
+        (Status::Ok, Some(answer)) => {
+            // multi-line answers, e.g. rendered letters, are cut to their first line.
+            let mut cell = answer.lines().next().unwrap_or_default().to_string();
+
+            if answer.contains('\n') {
+                cell.push('…');
+            }
+
+            match report.is_correct() {
+                Some(true) => cell + " ✔",
+                Some(false) => cell + " ✘",
+                None => cell,
+            }
+        }
+        (Status::TimedOut, _) => "⏱ timed out".into(),
+        (Status::Panicked(_), _) => "✖ panicked".into(),
+        _ => "✖".into(),
+    }
+}
+
+fn format_answer_table(sets: &[String], rows: &[AnswerRow]) -> String {
+    let header: Vec<String> = ["Day".to_string(), "Part".to_string()]
+        .into_iter()
+        .chain(sets.iter().cloned())
+        .collect();
+
+    let lines: Vec<Vec<String>> = std::iter::once(header)
+        .chain(rows.iter().map(|row| {
+            [row.day.to_string(), row.part.to_string()]
+                .into_iter()
+                .chain(row.cells.iter().cloned())
+                .collect()
+        }))
+        .collect();
+
+    let widths: Vec<usize> = (0..lines[0].len())
+        .map(|column| {
+            lines
+                .iter()
+                .map(|line| line[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    lines
+        .iter()
+        .map(|line| {
+            let cells: Vec<String> = line
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - cell.chars().count())))
+                .collect();
+            format!("| {} |\n", cells.join(" | "))
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
@@ -56,199 +513,436 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both building and invoking them as well as collecting their reports.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::{Error, Output, RunOptions};
+    use crate::template::{
+        alloc::ALLOC_FEATURE,
+        input::InputSource,
+        report::{PartReport, Status, FORMAT_ARG, JSON_FORMAT},
+        runner::format_report,
+        year::Year,
+    };
     use crate::Day;
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
+        collections::{BTreeMap, HashMap},
+        io::{BufRead, BufReader, Read},
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        sync::mpsc::{self, RecvTimeoutError, Sender},
         thread,
+        time::{Duration, Instant},
     };
-
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    use tinyjson::JsonValue;
+
+    /// Solution binaries stop themselves when they exceed their day limit.
+    /// A child that is still running after this grace period is killed.
+    const KILL_GRACE: Duration = Duration::from_secs(30);
+
+    /// Builds the solution binaries of the given days with a single cargo invocation.
+    /// Returns the executable of every day that compiled, the errors of the other days are printed by cargo.
+    pub fn build(
+        year: Year,
+        days: &[Day],
+        is_release: bool,
+        alloc: bool,
+    ) -> Result<BTreeMap<Day, PathBuf>, Error> {
+        if days.is_empty() {
+            return Ok(BTreeMap::new());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_names: Vec<String> = days.iter().map(|day| year.bin_name(*day)).collect();
+
+        // keep going past days that do not compile, so the other days still run.
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--keep-going",
+            "--message-format",
+            "json-render-diagnostics",
+        ];
+
+        for bin_name in &bin_names {
+            args.push("--bin");
+            args.push(bin_name);
+        }
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if alloc {
+            args.push("--features");
+            args.push(ALLOC_FEATURE);
         }
 
+        // cargo reports artifacts on stdout, diagnostics go to the terminal.
+        let output = Command::new("cargo")
+            .args(&args)
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        let messages =
+            String::from_utf8(output.stdout).map_err(|e| Error::Parser(e.to_string()))?;
+        let mut executables = parse_executables(&messages);
+
+        Ok(days
+            .iter()
+            .zip(&bin_names)
+            .filter_map(|(day, bin_name)| Some((*day, executables.remove(bin_name)?)))
+            .collect())
+    }
+
+    /// Reads the executables of compiled binaries from the JSON messages of cargo, keyed by target name.
+    fn parse_executables(messages: &str) -> HashMap<String, PathBuf> {
+        messages
+            .lines()
+            .filter_map(|line| {
+                let message: JsonValue = line.parse().ok()?;
+                let fields: &HashMap<String, JsonValue> = message.get()?;
+
+                let reason: &String = fields.get("reason")?.get()?;
+                if reason != "compiler-artifact" {
+                    return None;
+                }
+
+                let target: &HashMap<String, JsonValue> = fields.get("target")?.get()?;
+                let name: &String = target.get("name")?.get()?;
+                // libraries have no executable.
+                let executable: &String = fields.get("executable")?.get()?;
+
+                Some((name.clone(), PathBuf::from(executable)))
+            })
+            .collect()
+    }
+
+    /// Lines of the child, from both of its output streams.
+    enum Line {
+        Stdout(String),
+        Stderr(String),
+    }
+
+    fn forward_lines(
+        stream: impl Read + Send + 'static,
+        tx: Sender<Line>,
+        line: fn(String) -> Line,
+    ) {
+        thread::spawn(move || {
+            for text in BufReader::new(stream).lines().map_while(Result::ok) {
+                if tx.send(line(text)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Run the solution executable of a day, its output and reports are buffered in `output`.
+    pub fn run_solution(
+        executable: &Path,
+        input: Option<&InputSource>,
+        options: &RunOptions,
+        output: &mut Output,
+    ) -> Result<Vec<PartReport>, Error> {
+        // request machine-readable reports from the child.
+        let mut args = vec![FORMAT_ARG.to_string(), JSON_FORMAT.to_string()];
+
+        if options.is_timed {
+            // mirror `--time` flag and bench settings to child invocations.
+            args.push("--time".into());
+            args.extend(options.bench.to_args());
+        }
+
+        args.extend(options.timeouts.to_args());
+        args.extend(input.map(InputSource::to_args).unwrap_or_default());
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr and any output that is not a report, render reports as they arrive.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(executable)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
-
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        // read both streams on separate threads, so a child that stops responding can not block the runner.
+        let (tx, rx) = mpsc::channel();
+
+        forward_lines(
+            cmd.stdout.take().ok_or(Error::BrokenPipe)?,
+            tx.clone(),
+            Line::Stdout,
+        );
+        forward_lines(
+            cmd.stderr.take().ok_or(Error::BrokenPipe)?,
+            tx,
+            Line::Stderr,
+        );
+
+        let mut reports: Vec<PartReport> = vec![];
+
+        let deadline = options
+            .timeouts
+            .day_limit()
+            .map(|limit| (limit, Instant::now() + limit + KILL_GRACE));
+
+        loop {
+            let line = match deadline {
+                Some((limit, deadline)) => {
+                    match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                        Ok(line) => line,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                        Err(RecvTimeoutError::Timeout) => {
+                            cmd.kill()?;
+                            cmd.wait()?;
+
+                            let part = reports.last().map_or(1, |r| r.part + 1);
+                            let report = PartReport::timed_out(part, limit);
+                            output.print(format_report(&report));
+                            reports.push(report);
+
+                            // the output threads are left behind, they end once the pipes close.
+                            return Ok(reports);
+                        }
+                    }
+                }
+                None => match rx.recv() {
+                    Ok(line) => line,
+                    Err(_) => break,
+                },
+            };
+
+            match line {
+                Line::Stdout(line) => match PartReport::from_json(&line) {
+                    Some(report) => {
+                        output.print(format_report(&report));
+                        reports.push(report);
+                    }
+                    None => output.println(line),
+                },
+                Line::Stderr(line) => output.eprintln(line),
+            }
         }
 
-        thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(reports)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn collect_timings(reports: &[PartReport], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        for report in reports {
+            if report.status != Status::Ok {
+                continue;
+            }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+            let stats = report.stats;
+            let timing_str = Some(format!("{:.1?}", stats.mean));
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            match report.part {
+                0 => {
+                    timings.parse = timing_str;
+                    timings.parse_stats = Some(stats);
+                }
+                1 => {
+                    timings.part_1 = timing_str;
+                    timings.part_1_stats = Some(stats);
+                }
+                2 => {
+                    timings.part_2 = timing_str;
+                    timings.part_2_stats = Some(stats);
                 }
+                _ => continue,
+            }
 
-                timings.total_nanos += nanos;
-            });
+            timings.total_nanos += stats.mean.as_nanos() as f64;
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{collect_timings, parse_executables};
+        use std::{path::PathBuf, time::Duration};
 
         use crate::day;
+        use crate::template::{
+            report::{PartReport, Status},
+            test_helpers::report,
+        };
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
+            let res = collect_timings(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("0"), None, &[74, 74, 75]),
+                    report(2, Some("10"), None, &[74_130_000]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_1_stats.unwrap().samples, 3);
+            assert_eq!(res.part_2_stats.unwrap().samples, 1);
         }
 
         #[test]
         fn test_patterns_in_input() {
-            let res = parse_exec_time(
+            let answer = "@ @ @ ( ) ms (2s @ 5 samples)";
+            let line = report(1, Some(answer), None, &[100, 100]).to_json();
+            let res = collect_timings(&[PartReport::from_json(&line).unwrap()], day!(1));
+            assert_eq!(res.total_nanos, 100_f64);
+            assert_eq!(res.part_1.unwrap(), "100.0ns");
+        }
+
+        #[test]
+        fn test_parse_step() {
+            let res = collect_timings(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(0, None, None, &[1_500_000, 1_500_000]),
+                    report(1, Some("0"), None, &[100]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 1_500_100_f64);
+            assert_eq!(res.parse.unwrap(), "1.5ms");
+            assert_eq!(res.parse_stats.unwrap().samples, 2);
+            assert_eq!(res.part_1.unwrap(), "100.0ns");
         }
 
         #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
+        fn test_timed_out_parts() {
+            let res = collect_timings(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(1, Some("0"), None, &[100]),
+                    PartReport::timed_out(2, Duration::from_secs(10)),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.total_nanos, 100_f64);
+            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_2_stats.is_none(), true);
+        }
+
+        #[test]
+        fn test_panicked_parts() {
+            let mut panicked = report(1, None, None, &[100]);
+            panicked.status = Status::Panicked("boom".into());
+
+            let res = collect_timings(&[panicked, report(2, Some("10"), None, &[200])], day!(1));
+            assert_eq!(res.total_nanos, 200_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.unwrap(), "200.0ns");
+        }
+
+        #[test]
+        fn test_parse_executables() {
+            let messages = [
+                r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null,"fresh":true}"#,
+                r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"01"},"executable":"/repo/target/release/01","fresh":false}"#,
+                r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"2022-05"},"executable":"/repo/target/release/2022-05","fresh":true}"#,
+                r#"{"reason":"build-finished","success":false}"#,
+            ]
+            .join("\n");
+
+            let executables = parse_executables(&messages);
+            assert_eq!(executables.len(), 2);
+            assert_eq!(executables["01"], PathBuf::from("/repo/target/release/01"));
+            assert_eq!(
+                executables["2022-05"],
+                PathBuf::from("/repo/target/release/2022-05")
+            );
+        }
+
+        #[test]
+        fn test_missing_parts() {
+            let res = collect_timings(
+                &[report(1, None, None, &[100]), report(2, None, None, &[100])],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_answer_table, resolve_jobs, run_in_order, AnswerRow};
+    use crate::day;
+    use crate::template::test_helpers::report;
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn test_answer_rows() {
+        let rows = AnswerRow::from_reports(
+            day!(1),
+            &[
+                Some(vec![
+                    report(1, Some("142"), Some("142"), &[100]),
+                    report(2, Some("281"), Some("280"), &[100]),
+                ]),
+                Some(vec![
+                    report(1, Some("#..\n.#."), None, &[100]),
+                    report(2, None, None, &[100]),
+                ]),
+                None,
+            ],
+        );
+
+        assert_eq!(rows[0].cells, vec!["142 ✔", "#..…", "–"]);
+        assert_eq!(rows[1].cells, vec!["281 ✘", "✖", "–"]);
+    }
+
+    #[test]
+    fn test_answer_table() {
+        let rows = vec![AnswerRow {
+            day: day!(1),
+            part: 1,
+            cells: vec!["142 ✔".into(), "55".into()],
+        }];
+
+        assert_eq!(
+            format_answer_table(&["default".into(), "alice".into()], &rows),
+            "| Day | Part | default | alice |\n| 01  | 1    | 142 ✔   | 55    |\n"
+        );
+    }
+
+    #[test]
+    fn test_run_in_order() {
+        let mut emitted = vec![];
+
+        // later tasks finish first, they are still emitted in order.
+        run_in_order(
+            6,
+            3,
+            |index| {
+                thread::sleep(Duration::from_millis(30 - 5 * index as u64));
+                index * 10
+            },
+            |result| emitted.push(result),
+        );
+
+        assert_eq!(emitted, vec![0, 10, 20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn test_jobs() {
+        assert_eq!(resolve_jobs(Some(4), true), 4);
+        assert_eq!(resolve_jobs(Some(0), false), 1);
+    }
+}
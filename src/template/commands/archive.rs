@@ -0,0 +1,220 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::{
+    aoc_client::AocClientError,
+    launch_config::{self, LAUNCH_FILE},
+    year::{self, Year, CONFIG_FILE},
+};
+use crate::{all_days, Day};
+
+/// Kept in data folders, so they exist in a fresh checkout.
+const KEEP_FILE: &str = ".keep";
+
+/// Moves the solutions and data of the configured year into the namespaced layout of that year, e.g.
+/// `src/bin/01.rs` to `src/bin/2023-01.rs` and `data/inputs/01.txt` to `data/2023/inputs/01.txt`.
+/// The configured year is then advanced, so the top-level layout is free for the next year.
+pub fn handle(next: Option<u16>, dry_run: bool) {
+    let Some(year) = year::configured() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    let next = next.unwrap_or(year + 1);
+
+    if next == year {
+        eprintln!("The next year has to differ from the archived year {year}.");
+        process::exit(1);
+    }
+
+    let (current, archived) = (Year::default(), Year::namespaced(year));
+
+    let days: Vec<Day> = all_days()
+        .filter(|day| Path::new(&current.bin_path(*day)).exists())
+        .collect();
+
+    let data_files = list_data_files(&current.data_dir()).unwrap_or_else(|e| {
+        eprintln!("Failed to list data files: {e}");
+        process::exit(1);
+    });
+
+    if days.is_empty() && data_files.is_empty() {
+        println!("Nothing to archive for {year}.");
+        return;
+    }
+
+    let mut moves: Vec<(PathBuf, PathBuf)> = days
+        .iter()
+        .map(|day| {
+            (
+                current.bin_path(*day).into(),
+                archived.bin_path(*day).into(),
+            )
+        })
+        .collect();
+
+    for path in &data_files {
+        let relative = path.strip_prefix(current.data_dir()).unwrap_or(path);
+        moves.push((path.clone(), archived.data_dir().join(relative)));
+    }
+
+    // an earlier archive of the same year must not be overwritten, nothing is moved if anything is in the way.
+    let conflicts: Vec<&PathBuf> = moves
+        .iter()
+        .map(|(_, to)| to)
+        .filter(|to| to.exists())
+        .collect();
+
+    if !conflicts.is_empty() {
+        eprintln!("Failed to archive {year}, these files exist already:");
+        for path in conflicts {
+            eprintln!("  {}", path.display());
+        }
+        process::exit(1);
+    }
+
+    // archived days only find their data once the year is no longer the configured one.
+    // the config is checked before anything is moved, so a failed archive leaves the year as it was.
+    let (config, updated_config) = year::read_config_with(next).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to archive {year}, can not set {} in \"{CONFIG_FILE}\": {e}",
+            year::YEAR_ENV
+        );
+        process::exit(1);
+    });
+
+    if !dry_run {
+        if let Err(e) = fs::write(CONFIG_FILE, &updated_config) {
+            eprintln!("Failed to update \"{CONFIG_FILE}\": {e}");
+            process::exit(1);
+        }
+
+        if let Err((path, e)) = move_files(&moves) {
+            eprintln!("Failed to move \"{}\": {e}", path.display());
+
+            match fs::write(CONFIG_FILE, &config) {
+                Ok(()) => eprintln!("Moved all files back, nothing was archived."),
+                Err(e) => eprintln!(
+                    "Failed to restore \"{CONFIG_FILE}\": {e}. Set {} back to {year}.",
+                    year::YEAR_ENV
+                ),
+            }
+
+            process::exit(1);
+        }
+    }
+
+    let verb = if dry_run { "Would move" } else { "Moved" };
+
+    for (from, to) in &moves[..days.len()] {
+        println!(
+            "{verb} module file \"{}\" to \"{}\"",
+            from.display(),
+            to.display()
+        );
+    }
+
+    if !data_files.is_empty() {
+        println!(
+            "{verb} {} data files from \"{}\" to \"{}\"",
+            data_files.len(),
+            current.data_dir().display(),
+            archived.data_dir().display()
+        );
+    }
+
+    for day in &days {
+        match launch_config::move_day_file(*day, current, archived, dry_run) {
+            Ok(true) => {
+                let [debug, _] = launch_config::entry_names(archived, *day);
+                println!(
+                    "{verb} debug configurations of day {day} to \"{debug}\" in \"{LAUNCH_FILE}\""
+                );
+            }
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to update debug configurations in \"{LAUNCH_FILE}\": {e:?}");
+            }
+        }
+    }
+
+    println!("---");
+
+    if dry_run {
+        println!(
+            "Would set {} to {next} in \"{CONFIG_FILE}\".",
+            year::YEAR_ENV
+        );
+        println!("Dry run, no files were moved.");
+        return;
+    }
+
+    println!("Set {} to {next} in \"{CONFIG_FILE}\".", year::YEAR_ENV);
+    println!(
+        "🎄 Archived {} days of {year}. Type `cargo all --year {year}` to run them.",
+        days.len()
+    );
+}
+
+/// Lists the files of the configured year in the data folder. Folders of other years and `.keep` files are skipped.
+fn list_data_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    let mut folders = vec![dir.to_path_buf()];
+
+    while let Some(folder) = folders.pop() {
+        let entries = match fs::read_dir(&folder) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+            if path.is_dir() {
+                if !(folder == dir && is_year_folder(&name)) {
+                    folders.push(path);
+                }
+            } else if name != KEEP_FILE {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Data of namespaced years lives in folders like `data/2022`.
+fn is_year_folder(name: &str) -> bool {
+    name.len() == 4 && name.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Moves all files or none. If a move fails, the files moved so far are moved back.
+fn move_files(moves: &[(PathBuf, PathBuf)]) -> Result<(), (PathBuf, io::Error)> {
+    for (index, (from, to)) in moves.iter().enumerate() {
+        if let Err(e) = move_file(from, to) {
+            for (from, to) in moves[..index].iter().rev() {
+                if let Err(e) = move_file(to, from) {
+                    eprintln!("Failed to move \"{}\" back: {e}", to.display());
+                }
+            }
+
+            return Err((from.clone(), e));
+        }
+    }
+
+    Ok(())
+}
+
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(dir) = to.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::rename(from, to)
+}
@@ -1,15 +1,60 @@
-use crate::template::aoc_cli;
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    examples,
+    input::{InputSets, DEFAULT_INPUT_SET},
+    year::Year,
+};
 use crate::Day;
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(day: Day, year: Year, inputs: Option<InputSets>, force: bool) {
+    let Some(inputs) = inputs else {
+        if let Err(e) = aoc_client::download(year, day, force) {
+            eprintln!("failed to download day {day} of {year}: {e}");
+            process::exit(1);
+        };
+        extract_examples(year, day, force);
+        return;
+    };
+
+    let sessions = aoc_client::get_sessions().unwrap_or_else(|e| {
+        eprintln!("failed to read sessions: {e}");
         process::exit(1);
+    });
+
+    let available: Vec<String> = sessions.iter().map(|(name, _)| name.clone()).collect();
+    let mut failed = false;
+
+    // a failing set, e.g. because of an expired session, should not keep the other sets from downloading.
+    for set in inputs.resolve(&available) {
+        let result = if set == DEFAULT_INPUT_SET {
+            aoc_client::download(year, day, force).map(|()| extract_examples(year, day, force))
+        } else {
+            match sessions.iter().find(|(name, _)| *name == set) {
+                Some((_, session)) => aoc_client::download_set(year, day, &set, session, force),
+                None => Err(AocClientError::InputSetNotConfigured(set.clone())),
+            }
+        };
+
+        if let Err(e) = result {
+            eprintln!("failed to download day {day} of {year} for input set \"{set}\": {e}");
+            failed = true;
+        }
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if failed {
         process::exit(1);
-    };
+    }
+}
+
+/// Extracts examples from the downloaded puzzle description. A failure leaves the download intact.
+pub(crate) fn extract_examples(year: Year, day: Day, force: bool) {
+    match examples::extract(year, day, force) {
+        Ok(written) => {
+            for (kind, path) in written {
+                println!("🎄 Successfully wrote {kind} to \"{}\".", path.display());
+            }
+        }
+        Err(e) => eprintln!("failed to extract examples of day {day}: {e:?}"),
+    }
 }
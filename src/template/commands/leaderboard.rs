@@ -0,0 +1,52 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::{
+    aoc_client,
+    leaderboard::{format_day, format_standings, Leaderboard},
+    year::Year,
+    ANSI_BOLD, ANSI_RESET,
+};
+use crate::Day;
+
+/// Shows a private leaderboard, fetched by its id or read from a local JSON file.
+/// Without a day, the breakdown covers every day a member has a star for.
+pub fn handle(id: Option<u64>, file: Option<PathBuf>, year: Year, day: Option<Day>) {
+    let json = match (id, file) {
+        (_, Some(file)) => fs::read_to_string(&file).unwrap_or_else(|e| {
+            eprintln!("failed to read leaderboard \"{}\": {e}", file.display());
+            process::exit(1);
+        }),
+        (Some(id), None) => aoc_client::get_leaderboard(year, id).unwrap_or_else(|e| {
+            eprintln!("failed to fetch leaderboard {id} of {year}: {e}");
+            process::exit(1);
+        }),
+        (None, None) => {
+            eprintln!("Pass the id of a private leaderboard or --file <path>.");
+            process::exit(1);
+        }
+    };
+
+    let leaderboard = Leaderboard::parse(&json).unwrap_or_else(|e| {
+        eprintln!("failed to parse leaderboard: {e:?}");
+        process::exit(1);
+    });
+
+    println!(
+        "{ANSI_BOLD}🎄 {} ({} members){ANSI_RESET}\n",
+        leaderboard.year,
+        leaderboard.members.len()
+    );
+    print!("{}", format_standings(&leaderboard));
+
+    let days = match day {
+        Some(day) => vec![day],
+        None => leaderboard.started_days(),
+    };
+
+    for day in days {
+        match format_day(&leaderboard, day) {
+            Some(breakdown) => print!("\n{breakdown}"),
+            None => println!("\nNo stars for day {day} yet."),
+        }
+    }
+}
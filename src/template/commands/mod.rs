@@ -1,5 +1,8 @@
 pub mod all;
+pub mod archive;
 pub mod download;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod wait;
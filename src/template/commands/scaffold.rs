@@ -1,94 +1,299 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    env, fs,
+    path::{Path, PathBuf},
     process,
 };
 
+use crate::template::{
+    answers,
+    input::{self, DEFAULT_INPUT_SET},
+    integrity,
+    launch_config::{self, Change, LAUNCH_FILE},
+    module_template::{self, Placeholders},
+    year::Year,
+};
 use crate::Day;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
-
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+/// How existing files of a day are treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Creates missing files and keeps existing ones.
+    #[default]
+    Create,
+    /// Overwrites existing files. Downloaded inputs, filled-in examples and recorded answers are kept.
+    Force,
+    /// Only creates missing data files, i.e. the input, example and answers file. The module is left alone.
+    Repair,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScaffoldOptions {
+    pub mode: Mode,
+    /// Lists the planned actions without writing anything.
+    pub dry_run: bool,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Piece {
+    Module,
+    Input,
+    Example,
+    Answers,
+}
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+impl Piece {
+    fn name(self) -> &'static str {
+        match self {
+            Piece::Module => "module file",
+            Piece::Input => "input file",
+            Piece::Example => "example file",
+            Piece::Answers => "answers file",
+        }
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Create,
+    Overwrite,
+    Keep,
+    /// A missing file that the mode does not create.
+    Skip,
 }
-"#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+/// Decides what happens to a file, given its current contents (`None` if it does not exist).
+fn plan(piece: Piece, mode: Mode, existing: Option<&str>) -> Action {
+    match (piece, mode, existing) {
+        (Piece::Module, Mode::Repair, None) => Action::Skip,
+        (_, _, None) => Action::Create,
+        // inputs are downloaded, an empty file is no better than the existing one.
+        (Piece::Input, _, Some(_)) => Action::Keep,
+        // examples and accepted answers are filled in by hand or by `cargo download`, only empty ones are replaced.
+        (Piece::Example | Piece::Answers, Mode::Force, Some(existing)) if has_content(existing) => {
+            Action::Keep
+        }
+        (_, Mode::Force, Some(_)) => Action::Overwrite,
+        (_, _, Some(_)) => Action::Keep,
+    }
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Whether a data file holds more than blank lines and comments, e.g. the header of a new answers file.
+fn has_content(contents: &str) -> bool {
+    contents
+        .lines()
+        .map(str::trim)
+        .any(|line| !line.is_empty() && !line.starts_with('#'))
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates the module, input, example and answers files of a day, and its debug configurations for VS Code.
+/// The module is rendered from the named template of the project, or from the default template.
+/// Existing files are kept unless `--force` is passed, which still keeps data files with content.
+pub fn handle(day: Day, year: Year, template: Option<&str>, options: ScaffoldOptions) {
+    let pieces = [
+        (Piece::Module, PathBuf::from(year.bin_path(day))),
+        (Piece::Input, input::get_path(year, DEFAULT_INPUT_SET, day)),
+        (
+            Piece::Example,
+            year.data_path("examples", &format!("{day}.txt")),
+        ),
+        (
+            Piece::Answers,
+            relative(&answers::get_path(year, DEFAULT_INPUT_SET, day)),
+        ),
+    ];
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+    let mut module_kept = false;
+
+    for (piece, path) in pieces {
+        let existing = fs::read_to_string(&path).ok();
+        let action = plan(piece, options.mode, existing.as_deref());
+
+        let contents = match (piece, action) {
+            (Piece::Module, Action::Create | Action::Overwrite) => {
+                let template = module_template::load(template).unwrap_or_else(|e| {
+                    eprintln!("Failed to load module template: {e}");
+                    process::exit(1);
+                });
+                module_template::render(&template, &Placeholders::of_day(year, day))
+            }
+            (Piece::Answers, _) => format!(
+                "# Accepted answers of day {day}, e.g. `part_one = 142`. Solutions are checked against them.\n"
+            ),
+            _ => String::new(),
+        };
+
+        module_kept |= piece == Piece::Module && action == Action::Keep;
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+        report(piece, action, &path, options.dry_run);
+
+        if options.dry_run || !matches!(action, Action::Create | Action::Overwrite) {
+            continue;
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+
+        if let Err(e) = write_file(&path, &contents) {
+            eprintln!("Failed to write {}: {e}", piece.name());
             process::exit(1);
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    update_launch_config(day, year, options);
+
+    let year_arg = if year.is_namespaced() {
+        format!(" --year {year}")
+    } else {
+        String::new()
+    };
+
+    println!("---");
+
+    if options.dry_run {
+        println!("Dry run, no files were written.");
+        return;
+    }
+
+    if module_kept && options.mode == Mode::Create {
+        println!("Pass --force to overwrite existing files.");
+    }
+
+    // solutions can not run on an empty input, flag it until the input is downloaded.
+    let input_path = input::get_path(year, DEFAULT_INPUT_SET, day);
+    if let Err(problem) = integrity::check(&fs::read_to_string(&input_path).unwrap_or_default()) {
+        println!("⚠ The input is {problem}. Type `cargo download {day}{year_arg}` to fetch it.");
+    }
+
+    println!("🎄 Type `cargo solve {day}{year_arg}` to run your solution.");
+}
+
+fn report(piece: Piece, action: Action, path: &Path, dry_run: bool) {
+    let name = piece.name();
+    let path = path.display();
+
+    // data files start out empty, except for the answers file which explains itself.
+    let empty = if matches!(piece, Piece::Input | Piece::Example) {
+        "empty "
+    } else {
+        ""
+    };
+
+    match (action, dry_run) {
+        (Action::Create, false) => println!("Created {empty}{name} \"{path}\""),
+        (Action::Create, true) => println!("Would create {empty}{name} \"{path}\""),
+        (Action::Overwrite, false) => println!("Overwrote {name} \"{path}\""),
+        (Action::Overwrite, true) => println!("Would overwrite {name} \"{path}\""),
+        (Action::Keep, _) => println!("Kept existing {name} \"{path}\""),
+        (Action::Skip, _) => {
+            println!("Skipped missing {name} \"{path}\", --repair only creates data files")
         }
+    }
+}
+
+/// Adds debug configurations of the day for VS Code. A malformed launch file does not stop scaffolding.
+fn update_launch_config(day: Day, year: Year, options: ScaffoldOptions) {
+    // repairing only adds what is missing, entries that were edited by hand are kept.
+    let replace = options.mode != Mode::Repair;
+
+    let change = match launch_config::update_file(year, day, replace, options.dry_run) {
+        Ok(change) => change,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+            eprintln!("Failed to update debug configurations in \"{LAUNCH_FILE}\": {e:?}");
+            return;
         }
+    };
+
+    let [debug, test] = launch_config::entry_names(year, day);
+    let entries = format!("debug configurations \"{debug}\" and \"{test}\"");
+
+    match (change, options.dry_run) {
+        (Change::Added, false) => println!("Added {entries} to \"{LAUNCH_FILE}\""),
+        (Change::Added, true) => println!("Would add {entries} to \"{LAUNCH_FILE}\""),
+        (Change::Updated, false) => println!("Updated {entries} in \"{LAUNCH_FILE}\""),
+        (Change::Updated, true) => println!("Would update {entries} in \"{LAUNCH_FILE}\""),
+        (Change::Unchanged, _) => println!("Kept existing {entries} in \"{LAUNCH_FILE}\""),
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+fn write_file(path: &Path, contents: &str) -> Result<(), std::io::Error> {
+    // data folders of other years may not exist yet.
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, contents)
+}
+
+fn relative(path: &Path) -> PathBuf {
+    env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{plan, Action, Mode, Piece};
+
+    #[test]
+    fn creates_missing_files() {
+        for piece in [Piece::Module, Piece::Input, Piece::Example, Piece::Answers] {
+            assert_eq!(plan(piece, Mode::Create, None), Action::Create);
+            assert_eq!(plan(piece, Mode::Force, None), Action::Create);
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    #[test]
+    fn keeps_existing_files() {
+        assert_eq!(plan(Piece::Module, Mode::Create, Some("")), Action::Keep);
+        assert_eq!(
+            plan(Piece::Example, Mode::Create, Some("1\n")),
+            Action::Keep
+        );
+        assert_eq!(plan(Piece::Input, Mode::Create, Some("")), Action::Keep);
+    }
+
+    #[test]
+    fn overwrites_with_force() {
+        assert_eq!(
+            plan(Piece::Module, Mode::Force, Some("")),
+            Action::Overwrite
+        );
+        assert_eq!(
+            plan(Piece::Answers, Mode::Force, Some("")),
+            Action::Overwrite
+        );
+        // downloaded inputs are replaced by `cargo download --force`.
+        assert_eq!(plan(Piece::Input, Mode::Force, Some("1\n")), Action::Keep);
+    }
+
+    #[test]
+    fn keeps_data_with_force() {
+        assert_eq!(
+            plan(Piece::Example, Mode::Force, Some("1abc2\n")),
+            Action::Keep
+        );
+        assert_eq!(
+            plan(
+                Piece::Answers,
+                Mode::Force,
+                Some("# header\npart_one = 142\n")
+            ),
+            Action::Keep
+        );
+        assert_eq!(
+            plan(Piece::Answers, Mode::Force, Some("# header\n\n")),
+            Action::Overwrite
+        );
+        assert_eq!(
+            plan(Piece::Example, Mode::Force, Some("\n")),
+            Action::Overwrite
+        );
+    }
+
+    #[test]
+    fn repairs_data_files() {
+        assert_eq!(plan(Piece::Module, Mode::Repair, None), Action::Skip);
+        assert_eq!(plan(Piece::Module, Mode::Repair, Some("")), Action::Keep);
+        assert_eq!(plan(Piece::Example, Mode::Repair, None), Action::Create);
+        assert_eq!(plan(Piece::Answers, Mode::Repair, None), Action::Create);
+        assert_eq!(plan(Piece::Answers, Mode::Repair, Some("")), Action::Keep);
+    }
 }
@@ -1,14 +1,34 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
+use crate::template::{
+    alloc::ALLOC_FEATURE, bench_config::BenchOptions, input::InputSource, timeout::TimeoutOptions,
+    year::Year,
+};
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    year: Year,
+    release: bool,
+    time: bool,
+    bench: BenchOptions,
+    alloc: bool,
+    timeouts: TimeoutOptions,
+    input: Option<InputSource>,
+    submit_part: Option<u8>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), year.bin_name(day)];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
+    if alloc {
+        cmd_args.push("--features".to_string());
+        cmd_args.push(ALLOC_FEATURE.to_string());
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -18,6 +38,13 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
 
     if time {
         cmd_args.push("--time".to_string());
+        cmd_args.append(&mut bench.to_args());
+    }
+
+    cmd_args.append(&mut timeouts.to_args());
+
+    if let Some(input) = input {
+        cmd_args.append(&mut input.to_args());
     }
 
     let mut cmd = Command::new("cargo")
@@ -27,5 +54,10 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // pass the exit code of the solution on, e.g. to signal a rejected submission.
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
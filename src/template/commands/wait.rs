@@ -0,0 +1,123 @@
+use std::{
+    io::{stdout, Write},
+    process,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    commands::{
+        download,
+        scaffold::{self, ScaffoldOptions},
+    },
+    unlock::{format_remaining, next_unlock, unlock_time, wait_until, Clock},
+    year::Year,
+};
+use crate::Day;
+
+/// Waited on top of the unlock time, so the download does not race the website.
+const UNLOCK_MARGIN: Duration = Duration::from_secs(2);
+
+/// Waits for the puzzle of a day to unlock, then downloads and scaffolds it.
+/// Without a day, the next day of the year that unlocks is waited for.
+pub fn handle(year: Year, day: Option<Day>, clock: &impl Clock) {
+    let Some(year_number) = year.number() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    let Some((day, unlock)) = choose_day(year_number, day, clock) else {
+        eprintln!("All puzzles of {year} are unlocked. Pass a day to scaffold and download it.");
+        process::exit(1);
+    };
+
+    if unlock > clock.now() {
+        println!("🎄 Day {day} of {year} unlocks at midnight US Eastern.");
+
+        wait_until(clock, unlock + UNLOCK_MARGIN, |remaining| {
+            print!("\r⏳ Unlocking in {}... ", format_remaining(remaining));
+            let _ = stdout().flush();
+        });
+
+        println!("\r🔓 Day {day} is unlocked.                  ");
+    }
+
+    // downloading first lets the module template use the title and example answers of the puzzle.
+    // a failed download, e.g. without a session, does not keep the day from being scaffolded.
+    let downloaded = match aoc_client::download(year, day, false) {
+        Ok(()) => {
+            download::extract_examples(year, day, false);
+            true
+        }
+        Err(e) => {
+            eprintln!("failed to download day {day} of {year}: {e}");
+            false
+        }
+    };
+    println!("---");
+
+    // a day that was scaffolded ahead of time keeps its files.
+    scaffold::handle(day, year, None, ScaffoldOptions::default());
+
+    if !downloaded {
+        process::exit(1);
+    }
+}
+
+/// Picks the day to wait for and its unlock time. Without a day, the next day of the year that unlocks is picked.
+fn choose_day(year: u16, day: Option<Day>, clock: &impl Clock) -> Option<(Day, SystemTime)> {
+    match day {
+        Some(day) => Some((day, unlock_time(year, day))),
+        None => next_unlock(year, clock.now()),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::choose_day;
+    use crate::day;
+    use crate::template::{
+        test_helpers::at,
+        unlock::{unlock_time, Clock},
+    };
+    use std::time::{Duration, SystemTime};
+
+    struct FixedClock(SystemTime);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> SystemTime {
+            self.0
+        }
+
+        fn sleep(&self, _: Duration) {}
+    }
+
+    fn fixed_at(secs: u64) -> FixedClock {
+        FixedClock(at(secs))
+    }
+
+    #[test]
+    fn chooses_next_unlocking_day() {
+        // before December, day 1 is next.
+        assert_eq!(
+            choose_day(2023, None, &fixed_at(1_700_000_000)),
+            Some((day!(1), unlock_time(2023, day!(1))))
+        );
+        // an hour after day 5 unlocked, day 6 is next.
+        let clock = FixedClock(unlock_time(2023, day!(5)) + Duration::from_secs(3600));
+        assert_eq!(
+            choose_day(2023, None, &clock),
+            Some((day!(6), unlock_time(2023, day!(6))))
+        );
+        // after day 25, nothing is left to wait for.
+        assert_eq!(choose_day(2023, None, &fixed_at(1_750_000_000)), None);
+    }
+
+    #[test]
+    fn chooses_given_day() {
+        assert_eq!(
+            choose_day(2023, Some(day!(3)), &fixed_at(1_750_000_000)),
+            Some((day!(3), unlock_time(2023, day!(3))))
+        );
+    }
+}
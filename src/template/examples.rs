@@ -0,0 +1,295 @@
+/// Extracts the examples of a day from its downloaded puzzle description in `data/puzzles/{day}.md`.
+/// The first code block of each part is taken as its example and the last highlighted value as its expected answer.
+/// Examples are written to `data/examples/{day}.txt` (`{day}-2.txt` if part two has its own example), and the
+/// expected answers to `data/examples/{day}.toml`.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{answers::Answers, aoc_client::get_puzzle_path, integrity, year::Year};
+use crate::Day;
+
+const EXAMPLES_FOLDER: &str = "examples";
+const PART_TWO_HEADING: &str = "## --- Part Two ---";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The example of a part. Part two often reuses the input of part one.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: Option<String>,
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub part_one: Option<Example>,
+    pub part_two: Option<Example>,
+}
+
+impl Examples {
+    #[must_use]
+    pub fn parse(markdown: &str) -> Self {
+        let (part_one, part_two) = match markdown.find(PART_TWO_HEADING) {
+            Some(index) => (&markdown[..index], Some(&markdown[index..])),
+            None => (markdown, None),
+        };
+
+        Self {
+            part_one: parse_part(part_one),
+            part_two: part_two.and_then(parse_part),
+        }
+    }
+
+    /// The input of part two, if it differs from the one of part one.
+    #[must_use]
+    pub fn part_two_input(&self) -> Option<&String> {
+        let one = self.part_one.as_ref().and_then(|x| x.input.as_ref());
+        let two = self.part_two.as_ref().and_then(|x| x.input.as_ref());
+        two.filter(|two| Some(*two) != one)
+    }
+
+    #[must_use]
+    pub fn answers(&self) -> Answers {
+        Answers {
+            part_one: self.part_one.as_ref().and_then(|x| x.answer.clone()),
+            part_two: self.part_two.as_ref().and_then(|x| x.answer.clone()),
+        }
+    }
+}
+
+fn parse_part(section: &str) -> Option<Example> {
+    let mut example = Example::default();
+    let mut block: Option<String> = None;
+
+    for line in section.lines() {
+        if line.trim() == "```" {
+            match block.take() {
+                Some(code) => {
+                    example.input.get_or_insert(code);
+                }
+                None => block = Some(String::new()),
+            }
+            continue;
+        }
+
+        match &mut block {
+            Some(code) => {
+                code.push_str(line);
+                code.push('\n');
+            }
+            None => {
+                if let Some(answer) = last_highlighted(line) {
+                    example.answer = Some(answer);
+                }
+            }
+        }
+    }
+
+    (example != Example::default()).then_some(example)
+}
+
+/// Finds the last highlighted inline code of a line, i.e. `` `*142*` `` or `` *`142`* ``.
+fn last_highlighted(line: &str) -> Option<String> {
+    let segments: Vec<&str> = line.split('`').collect();
+
+    // inline code spans are the odd segments.
+    (1..segments.len().saturating_sub(1))
+        .step_by(2)
+        .filter_map(|i| {
+            let code = segments[i];
+            let inner = code.strip_prefix('*').and_then(|x| x.strip_suffix('*'));
+
+            match inner {
+                Some(inner) if !inner.is_empty() => Some(inner.to_string()),
+                _ if segments[i - 1].ends_with('*') && segments[i + 1].starts_with('*') => {
+                    Some(code.to_string())
+                }
+                _ => None,
+            }
+        })
+        .next_back()
+}
+
+#[must_use]
+pub fn get_answers_path(year: Year, day: Day) -> PathBuf {
+    year.data_path(EXAMPLES_FOLDER, &format!("{day}.toml"))
+}
+
+/// Extracts the examples of a day and writes them. Returns the kind and path of each written file.
+/// Examples that exist already are kept unless `force` is set, they may have been edited by hand.
+pub fn extract(year: Year, day: Day, force: bool) -> Result<Vec<(&'static str, PathBuf)>, Error> {
+    let examples = Examples::parse(&fs::read_to_string(get_puzzle_path(year, day))?);
+    let mut written = vec![];
+
+    let inputs = [
+        (
+            examples.part_one.as_ref().and_then(|x| x.input.as_ref()),
+            format!("{day}.txt"),
+        ),
+        (examples.part_two_input(), format!("{day}-2.txt")),
+    ];
+
+    for (input, file) in inputs {
+        let Some(input) = input else {
+            continue;
+        };
+
+        let path = year.data_path(EXAMPLES_FOLDER, &file);
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+
+        if force || integrity::check(&existing).is_err() {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, input)?;
+            written.push(("example", path));
+        }
+    }
+
+    let path = get_answers_path(year, day);
+    if write_answers(&path, examples.answers(), force)? {
+        written.push(("example answers", path));
+    }
+
+    Ok(written)
+}
+
+/// Writes the extracted answers to `path`. Returns whether the file changed.
+/// Answers of part two only show up after part one is solved, so known answers are completed, not replaced.
+fn write_answers(path: &Path, extracted: Answers, force: bool) -> Result<bool, Error> {
+    let existing = match fs::read_to_string(path) {
+        Ok(contents) if !force => {
+            Answers::parse(&contents).map_err(|_| Error::Parser(path.display().to_string()))?
+        }
+        _ => Answers::default(),
+    };
+
+    let answers = Answers {
+        part_one: existing.part_one.clone().or(extracted.part_one),
+        part_two: existing.part_two.clone().or(extracted.part_two),
+    };
+
+    if answers == existing {
+        return Ok(false);
+    }
+
+    // the examples folder of a year may not exist yet if no example input was written.
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, answers.to_toml())?;
+    Ok(true)
+}
+
+/// Reads the expected answer of an example, e.g. for `assert_eq!` in the tests of a solution.
+#[must_use]
+pub fn read_answer(day: Day, part: u8) -> Option<String> {
+    let contents = fs::read_to_string(get_answers_path(Year::of_binary(), day)).ok()?;
+    Answers::parse(&contents).ok()?.get(part).cloned()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{last_highlighted, write_answers, Example, Examples};
+    use crate::template::answers::Answers;
+    use std::{env, fs, process};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values are `12` and `38`. Adding these together produces `*50*`.
+
+## --- Part Two ---
+
+For example:
+
+```
+two1nine
+eightwothree
+```
+
+In this example, the calibration values are `29` and `83`. Adding these together produces *`112`*.
+";
+
+    #[test]
+    fn parses_examples() {
+        let examples = Examples::parse(PUZZLE);
+
+        assert_eq!(
+            examples.part_one,
+            Some(Example {
+                input: Some("1abc2\npqr3stu8vwx\n".into()),
+                answer: Some("50".into()),
+            })
+        );
+        assert_eq!(
+            examples.part_two_input().map(String::as_str),
+            Some("two1nine\neightwothree\n")
+        );
+        assert_eq!(examples.answers().part_two.as_deref(), Some("112"));
+    }
+
+    #[test]
+    fn parses_unsolved_puzzles() {
+        let (part_one, _) = PUZZLE.split_once("## --- Part Two ---").unwrap();
+        let examples = Examples::parse(part_one);
+
+        assert!(examples.part_one.is_some());
+        assert_eq!(examples.part_two, None);
+        assert_eq!(examples.part_two_input(), None);
+    }
+
+    #[test]
+    fn reuses_input_of_part_one() {
+        let puzzle = "```\n1\n```\nresult `*1*`\n## --- Part Two ---\nNow the result is `*2*`.\n";
+        let examples = Examples::parse(puzzle);
+
+        assert_eq!(examples.part_two_input(), None);
+        assert_eq!(examples.answers().part_one.as_deref(), Some("1"));
+        assert_eq!(examples.answers().part_two.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn finds_highlighted_values() {
+        assert_eq!(
+            last_highlighted("`*1*` then `x` and `*42*`."),
+            Some("42".into())
+        );
+        assert_eq!(last_highlighted("a `*` b `**` c"), None);
+        assert_eq!(last_highlighted("plain `42`"), None);
+    }
+
+    #[test]
+    fn writes_answers_into_missing_folder() {
+        let root = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+        let path = root.join("2023").join("examples").join("01.toml");
+
+        let extracted = Answers {
+            part_one: Some("142".into()),
+            part_two: None,
+        };
+        assert!(write_answers(&path, extracted, false).unwrap());
+
+        let written = Answers::parse(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written.part_one.as_deref(), Some("142"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,299 @@
+/// Selects the input a solution runs against.
+/// By default, solutions read `data/inputs/{day}.txt`. `--input <path>` reads another file instead, `--stdin` reads piped data.
+/// `--input-set <name>` reads the puzzle input of another account from `data/inputs/{name}/{day}.txt`.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crate::template::{integrity, year::Year};
+use crate::Day;
+
+const INPUT_ARG: &str = "--input";
+const STDIN_ARG: &str = "--stdin";
+const INPUT_SET_ARG: &str = "--input-set";
+
+const INPUTS_FOLDER: &str = "inputs";
+
+/// Name of the input set that holds the puzzle inputs of the configured session.
+pub const DEFAULT_INPUT_SET: &str = "default";
+
+/// An input other than the puzzle input of the day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    /// A named input set, i.e. the puzzle inputs of another account.
+    Set(String),
+}
+
+impl InputSource {
+    /// Reads the input source from command-line arguments. `None` if the puzzle input should be used.
+    pub fn from_args(args: &[String]) -> Result<Option<Self>, String> {
+        let file = arg_value(args, INPUT_ARG, "a path")?;
+        let set = arg_value(args, INPUT_SET_ARG, "a name")?;
+        let stdin = args.iter().any(|x| x == STDIN_ARG);
+
+        match (file, set, stdin) {
+            (None, None, false) => Ok(None),
+            (Some(path), None, false) => Ok(Some(Self::File(path.into()))),
+            (None, Some(set), false) => Ok(Some(Self::Set(set.clone()))),
+            (None, None, true) => Ok(Some(Self::Stdin)),
+            _ => Err(format!(
+                "{INPUT_ARG}, {INPUT_SET_ARG} and {STDIN_ARG} can not be combined"
+            )),
+        }
+    }
+
+    /// Converts the source back to command-line arguments, so it can be mirrored to child invocations.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::File(path) => vec![INPUT_ARG.into(), path.display().to_string()],
+            Self::Stdin => vec![STDIN_ARG.into()],
+            Self::Set(set) => vec![INPUT_SET_ARG.into(), set.clone()],
+        }
+    }
+
+    pub fn read(&self, day: Day) -> io::Result<String> {
+        match self.path(day) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// The file the input is read from, `None` for stdin.
+    #[must_use]
+    pub fn path(&self, day: Day) -> Option<PathBuf> {
+        match self {
+            Self::File(path) => Some(path.clone()),
+            Self::Set(set) => Some(get_path(Year::of_binary(), set, day)),
+            Self::Stdin => None,
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "\"{}\"", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+            Self::Set(set) => write!(f, "input set \"{set}\""),
+        }
+    }
+}
+
+fn arg_value<'a>(
+    args: &'a [String],
+    name: &str,
+    expected: &str,
+) -> Result<Option<&'a String>, String> {
+    match args.iter().position(|x| x == name) {
+        Some(index) => args
+            .get(index + 1)
+            .filter(|x| !x.starts_with("--"))
+            .map(Some)
+            .ok_or_else(|| format!("expecting {expected} after {name}")),
+        None => Ok(None),
+    }
+}
+
+/// Selects input sets for `download` and `all`, e.g. `--inputs all` or `--inputs default,alice`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSets {
+    All,
+    Named(Vec<String>),
+}
+
+impl FromStr for InputSets {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Self::All);
+        }
+
+        let names: Vec<String> = s.split(',').map(|x| x.trim().to_string()).collect();
+
+        match names.iter().find(|x| !is_valid_set_name(x)) {
+            Some(name) => Err(format!("\"{name}\" is not a valid input set name")),
+            None => Ok(Self::Named(names)),
+        }
+    }
+}
+
+impl InputSets {
+    /// Resolves the selection against the available sets. The default set comes first.
+    #[must_use]
+    pub fn resolve(&self, available: &[String]) -> Vec<String> {
+        match self {
+            Self::All => std::iter::once(DEFAULT_INPUT_SET.to_string())
+                .chain(
+                    available
+                        .iter()
+                        .filter(|x| *x != DEFAULT_INPUT_SET)
+                        .cloned(),
+                )
+                .collect(),
+            Self::Named(names) => names.clone(),
+        }
+    }
+}
+
+/// Input set names become folder names, so they are limited to letters, digits, `-` and `_`.
+#[must_use]
+pub fn is_valid_set_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Path of a day's input in an input set, e.g. `data/inputs/alice/01.txt` or `data/inputs/01.txt` for the default set.
+#[must_use]
+pub fn get_path(year: Year, set: &str, day: Day) -> PathBuf {
+    let folder = year.data_dir().join(INPUTS_FOLDER);
+
+    if set == DEFAULT_INPUT_SET {
+        folder.join(format!("{day}.txt"))
+    } else {
+        folder.join(set).join(format!("{day}.txt"))
+    }
+}
+
+/// Lists the named input sets of a year, i.e. the folders in its `inputs` folder.
+#[must_use]
+pub fn list_sets(year: Year) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(year.data_dir().join(INPUTS_FOLDER)) else {
+        return vec![];
+    };
+
+    let mut sets: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| is_valid_set_name(name))
+        .collect();
+
+    sets.sort();
+    sets
+}
+
+/// Reads the input of a solution binary, honoring `--input`, `--input-set` and `--stdin`.
+/// Exits if the input is missing or obviously invalid, e.g. empty, instead of handing it to the solution.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+    let year = Year::of_binary();
+
+    let source = InputSource::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+
+    // puzzle inputs and input sets are downloaded, so they can be fixed by downloading them again.
+    let (source, download_args) = match source {
+        None => (
+            InputSource::File(get_path(year, DEFAULT_INPUT_SET, day)),
+            Some(String::new()),
+        ),
+        Some(InputSource::Set(set)) => {
+            let download_args = format!(" --inputs {set}");
+            (InputSource::Set(set), Some(download_args))
+        }
+        Some(source) => (source, None),
+    };
+
+    let hint = download_args.map_or_else(String::new, |args| {
+        let year_arg = if year.is_namespaced() {
+            format!(" --year {year}")
+        } else {
+            String::new()
+        };
+        format!(" Download it with `cargo download {day}{year_arg}{args}`.")
+    });
+
+    let input = source.read(day).unwrap_or_else(|e| {
+        eprintln!("✘ Could not read input {source}: {e}.{hint}");
+        std::process::exit(1);
+    });
+
+    if let Err(problem) = integrity::check(&input) {
+        eprintln!("✘ The input {source} is {problem}.{hint}");
+        std::process::exit(1);
+    }
+
+    if let Some(path) = source.path(day) {
+        if integrity::verify_checksum(&path, &input) == Some(false) {
+            eprintln!("⚠ The input {source} was changed after it was downloaded.");
+        }
+    }
+
+    input
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputSets, InputSource};
+    use crate::template::test_helpers::args;
+
+    #[test]
+    fn parses_args() {
+        assert_eq!(InputSource::from_args(&args("01 --time")), Ok(None));
+        assert_eq!(
+            InputSource::from_args(&args("01 --stdin")),
+            Ok(Some(InputSource::Stdin))
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --input edge.txt --time")),
+            Ok(Some(InputSource::File("edge.txt".into())))
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --input-set alice")),
+            Ok(Some(InputSource::Set("alice".into())))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_args() {
+        assert!(InputSource::from_args(&args("01 --input")).is_err());
+        assert!(InputSource::from_args(&args("01 --input --time")).is_err());
+        assert!(InputSource::from_args(&args("01 --input a.txt --stdin")).is_err());
+        assert!(InputSource::from_args(&args("01 --input-set alice --stdin")).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        let source = InputSource::File("data/inputs/teammate.txt".into());
+        assert_eq!(InputSource::from_args(&source.to_args()), Ok(Some(source)));
+
+        let source = InputSource::Set("alice".into());
+        assert_eq!(InputSource::from_args(&source.to_args()), Ok(Some(source)));
+    }
+
+    #[test]
+    fn resolves_input_sets() {
+        let available = vec!["alice".to_string(), "bob".to_string()];
+        assert_eq!(
+            "all".parse::<InputSets>().unwrap().resolve(&available),
+            vec!["default", "alice", "bob"]
+        );
+        assert_eq!(
+            "bob,default"
+                .parse::<InputSets>()
+                .unwrap()
+                .resolve(&available),
+            vec!["bob", "default"]
+        );
+        assert!("alice,../bob".parse::<InputSets>().is_err());
+        assert!("alice,".parse::<InputSets>().is_err());
+    }
+}
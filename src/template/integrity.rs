@@ -0,0 +1,143 @@
+/// Checks puzzle inputs for obviously invalid content, e.g. empty files or error pages of the website.
+/// Downloaded inputs are recorded with a checksum in a `checksums.txt` file next to them, so inputs that are
+/// changed after downloading are noticed.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const CHECKSUMS_FILE: &str = "checksums.txt";
+const CHECKSUM_PREFIX: &str = "fnv1a64:";
+
+/// Responses of the website that are not puzzle inputs, e.g. when the session is missing or the day is locked.
+const ERROR_MESSAGES: [&str; 3] = [
+    "Puzzle inputs differ by user",
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "404 Not Found",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    Empty,
+    Html,
+    /// An error message of the website instead of an input.
+    ErrorMessage(String),
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Empty => write!(f, "empty"),
+            Problem::Html => write!(f, "an HTML page"),
+            Problem::ErrorMessage(message) => {
+                write!(f, "an error message of the website: \"{message}\"")
+            }
+        }
+    }
+}
+
+/// Checks that the contents of an input look like a puzzle input.
+pub fn check(contents: &str) -> Result<(), Problem> {
+    let trimmed = contents.trim();
+
+    if trimmed.is_empty() {
+        return Err(Problem::Empty);
+    }
+
+    let start = trimmed.chars().take(15).collect::<String>().to_lowercase();
+
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        return Err(Problem::Html);
+    }
+
+    // error messages are a single line, inputs that happen to contain these words are longer.
+    if !trimmed.contains('\n') && ERROR_MESSAGES.iter().any(|x| trimmed.starts_with(x)) {
+        return Err(Problem::ErrorMessage(trimmed.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Checksum of an input, e.g. `fnv1a64:af63dc4c8601ec8c`.
+#[must_use]
+pub fn checksum(contents: &str) -> String {
+    let hash = contents
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    format!("{CHECKSUM_PREFIX}{hash:016x}")
+}
+
+/// Stores the checksum of an input that was just written to `path`.
+pub fn record_checksum(path: &Path, contents: &str) -> io::Result<()> {
+    let (checksums_path, name) = get_checksums_path(path)?;
+
+    let mut lines: Vec<String> = fs::read_to_string(&checksums_path)
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| line.split_whitespace().next() != Some(&name))
+        .map(String::from)
+        .collect();
+
+    lines.push(format!("{name} {}", checksum(contents)));
+    lines.sort();
+
+    fs::write(checksums_path, lines.join("\n") + "\n")
+}
+
+/// Compares an input with the checksum recorded when it was downloaded.
+/// `None` if no checksum is known, e.g. for inputs that were not downloaded.
+#[must_use]
+pub fn verify_checksum(path: &Path, contents: &str) -> Option<bool> {
+    let (checksums_path, name) = get_checksums_path(path).ok()?;
+    let checksums = fs::read_to_string(checksums_path).ok()?;
+
+    checksums.lines().find_map(|line| {
+        let (file, expected) = line.split_once(' ')?;
+        (file == name).then(|| expected.trim() == checksum(contents))
+    })
+}
+
+fn get_checksums_path(path: &Path) -> io::Result<(PathBuf, String)> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidInput, "not a file path");
+
+    let name = path.file_name().ok_or_else(invalid)?.to_string_lossy();
+    let dir = path.parent().ok_or_else(invalid)?;
+
+    Ok((dir.join(CHECKSUMS_FILE), name.to_string()))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, checksum, Problem};
+
+    #[test]
+    fn accepts_inputs() {
+        assert_eq!(check("1abc2\npqr3stu8vwx\n"), Ok(()));
+        assert_eq!(check("<<>>\n"), Ok(()));
+    }
+
+    #[test]
+    fn detects_invalid_inputs() {
+        assert_eq!(check(""), Err(Problem::Empty));
+        assert_eq!(check(" \n\n"), Err(Problem::Empty));
+        assert_eq!(
+            check("<!DOCTYPE html>\n<html lang=\"en-us\"></html>"),
+            Err(Problem::Html)
+        );
+        assert!(matches!(
+            check("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            Err(Problem::ErrorMessage(_))
+        ));
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(checksum(""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(checksum("a"), "fnv1a64:af63dc4c8601ec8c");
+        assert_ne!(checksum("1\n2\n"), checksum("1\n2"));
+    }
+}
@@ -0,0 +1,299 @@
+/// Module that keeps a journal of submitted answers in `data/submissions/` (or the folder of the year).
+/// Before submitting, the journal is checked so answers that are known to be wrong are not sent again.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    json::{object, OrderedJson},
+    verdict::Verdict,
+    year::Year,
+};
+use crate::Day;
+
+/// Version of the journal file format. Bump this when making incompatible changes.
+const VERSION: f64 = 1.0;
+
+const JOURNAL_FOLDER: &str = "submissions";
+const JOURNAL_FILE: &str = "journal.json";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn new(day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp,
+        }
+    }
+}
+
+/// Why an answer is not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyRejected,
+    /// The answer is not below an answer that was too high.
+    AboveTooHigh(String),
+    /// The answer is not above an answer that was too low.
+    BelowTooLow(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyRejected => write!(f, "this answer was rejected before."),
+            Refusal::AboveTooHigh(bound) => {
+                write!(f, "{bound} was too high, this answer is not lower.")
+            }
+            Refusal::BelowTooLow(bound) => {
+                write!(f, "{bound} was too low, this answer is not higher.")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Journal {
+    pub submissions: Vec<Submission>,
+}
+
+impl Journal {
+    /// Checks an answer against earlier submissions of the same part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let previous = self
+            .submissions
+            .iter()
+            .filter(|s| s.day == day && s.part == part);
+
+        let answer = answer.trim();
+        let value = answer.parse::<i128>().ok();
+
+        for submission in previous {
+            if submission.answer.trim() == answer && submission.verdict.is_wrong() {
+                return Err(Refusal::AlreadyRejected);
+            }
+
+            // bounds only apply to numeric answers.
+            let (Some(value), Ok(bound)) = (value, submission.answer.trim().parse::<i128>()) else {
+                continue;
+            };
+
+            match submission.verdict {
+                Verdict::TooHigh if value >= bound => {
+                    return Err(Refusal::AboveTooHigh(submission.answer.clone()))
+                }
+                Verdict::TooLow if value <= bound => {
+                    return Err(Refusal::BelowTooLow(submission.answer.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn to_json(&self) -> OrderedJson {
+        let submissions: Vec<OrderedJson> = self
+            .submissions
+            .iter()
+            .map(|s| {
+                #[allow(clippy::cast_precision_loss)]
+                object(vec![
+                    ("day", f64::from(s.day.into_inner()).into()),
+                    ("part", f64::from(s.part).into()),
+                    ("answer", s.answer.clone().into()),
+                    ("verdict", s.verdict.as_str().to_string().into()),
+                    ("timestamp", (s.timestamp as f64).into()),
+                ])
+            })
+            .collect();
+
+        object(vec![
+            ("version", VERSION.into()),
+            ("submissions", submissions.into()),
+        ])
+    }
+
+    fn from_json(value: &JsonValue) -> Result<Self, Error> {
+        let invalid = || Error::Parser("Malformed submission journal.".into());
+
+        let version: &f64 = value["version"].get().ok_or_else(invalid)?;
+        if *version > VERSION {
+            return Err(Error::Parser(format!(
+                "Submission journal has version {version}, only versions up to {VERSION} are supported."
+            )));
+        }
+
+        let submissions: &Vec<JsonValue> = value["submissions"].get().ok_or_else(invalid)?;
+
+        let submissions = submissions
+            .iter()
+            .map(|s| {
+                let day: &f64 = s["day"].get()?;
+                let part: &f64 = s["part"].get()?;
+                let answer: &String = s["answer"].get()?;
+                let verdict: &String = s["verdict"].get()?;
+                let timestamp: &f64 = s["timestamp"].get()?;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Some(Submission {
+                    day: Day::new(*day as u8)?,
+                    part: *part as u8,
+                    answer: answer.clone(),
+                    verdict: Verdict::from_str(verdict)?,
+                    timestamp: *timestamp as u64,
+                })
+            })
+            .collect::<Option<Vec<Submission>>>()
+            .ok_or_else(invalid)?;
+
+        Ok(Self { submissions })
+    }
+}
+
+fn get_path(year: Year) -> PathBuf {
+    year.data_path(JOURNAL_FOLDER, JOURNAL_FILE)
+}
+
+/// Loads the journal of a year. A missing file yields an empty journal.
+pub fn load(year: Year) -> Result<Journal, Error> {
+    let path = get_path(year);
+
+    if !path.exists() {
+        return Ok(Journal::default());
+    }
+
+    let contents = fs::read_to_string(path)?;
+    let value: JsonValue = contents
+        .parse()
+        .map_err(|e| Error::Parser(format!("{e}")))?;
+
+    Journal::from_json(&value)
+}
+
+pub fn save(year: Year, journal: &Journal) -> Result<(), Error> {
+    let path = get_path(year);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let json = journal
+        .to_json()
+        .format()
+        .map_err(|e| Error::Parser(format!("{e}")))?;
+
+    fs::write(path, json + "\n")?;
+    Ok(())
+}
+
+/// Appends a submission to the stored journal of a year.
+pub fn record(year: Year, submission: Submission) -> Result<(), Error> {
+    let mut journal = load(year)?;
+    journal.submissions.push(submission);
+    save(year, &journal)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Journal, Refusal, Submission};
+    use crate::day;
+    use crate::template::verdict::Verdict;
+
+    fn journal(submissions: &[(&str, Verdict)]) -> Journal {
+        Journal {
+            submissions: submissions
+                .iter()
+                .map(|(answer, verdict)| Submission {
+                    day: day!(5),
+                    part: 1,
+                    answer: (*answer).to_string(),
+                    verdict: verdict.clone(),
+                    timestamp: 1_700_000_000,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let journal = journal(&[("abc", Verdict::Wrong), ("42", Verdict::RateLimited(None))]);
+        assert_eq!(
+            journal.check(day!(5), 1, "abc"),
+            Err(Refusal::AlreadyRejected)
+        );
+        assert_eq!(journal.check(day!(5), 1, "42"), Ok(()));
+        assert_eq!(journal.check(day!(5), 2, "abc"), Ok(()));
+        assert_eq!(journal.check(day!(6), 1, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let journal = journal(&[("100", Verdict::TooHigh), ("10", Verdict::TooLow)]);
+        assert_eq!(
+            journal.check(day!(5), 1, "100"),
+            Err(Refusal::AlreadyRejected)
+        );
+        assert_eq!(
+            journal.check(day!(5), 1, "250"),
+            Err(Refusal::AboveTooHigh("100".into()))
+        );
+        assert_eq!(
+            journal.check(day!(5), 1, "-3"),
+            Err(Refusal::BelowTooLow("10".into()))
+        );
+        assert_eq!(journal.check(day!(5), 1, "50"), Ok(()));
+        assert_eq!(journal.check(day!(5), 1, "not a number"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_journal() {
+        let journal = journal(&[("100", Verdict::TooHigh), ("35", Verdict::Correct)]);
+        let json = journal.to_json().format().unwrap();
+        let parsed = Journal::from_json(&json.parse().unwrap()).unwrap();
+        assert_eq!(parsed, journal);
+    }
+
+    #[test]
+    fn writes_keys_in_order() {
+        let journal = journal(&[("100", Verdict::TooHigh)]);
+        let json = journal.to_json().format().unwrap();
+
+        // a versioned file must not change between saves of the same journal.
+        assert_eq!(json, journal.to_json().format().unwrap());
+        assert!(json.starts_with("{\n  \"version\": 1,\n  \"submissions\": ["));
+        assert!(json.contains("\"day\": 5,\n      \"part\": 1,\n      \"answer\": \"100\""));
+    }
+}
@@ -0,0 +1,139 @@
+/// JSON documents that keep the order of their keys.
+/// tinyjson stores objects in a `HashMap`, so versioned files written through it would list their keys in a different order on every save.
+use tinyjson::{JsonGenerateError, JsonValue};
+
+const INDENT: &str = "  ";
+
+/// A JSON value whose objects are written with their keys in the given order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderedJson {
+    Value(JsonValue),
+    Array(Vec<OrderedJson>),
+    Object(Vec<(String, OrderedJson)>),
+}
+
+impl OrderedJson {
+    /// Formats the value with 2-space indentation, the same way as [`JsonValue::format`].
+    pub fn format(&self) -> Result<String, JsonGenerateError> {
+        let mut out = String::new();
+        self.write(&mut out, 0)?;
+        Ok(out)
+    }
+
+    fn write(&self, out: &mut String, level: usize) -> Result<(), JsonGenerateError> {
+        match self {
+            OrderedJson::Value(value) => {
+                out.push_str(&value.stringify()?);
+                Ok(())
+            }
+            OrderedJson::Array(items) => write_nested(
+                out,
+                level,
+                ('[', ']'),
+                items.iter().map(|item| (None, item)),
+            ),
+            OrderedJson::Object(pairs) => write_nested(
+                out,
+                level,
+                ('{', '}'),
+                pairs.iter().map(|(key, value)| (Some(key), value)),
+            ),
+        }
+    }
+}
+
+fn write_nested<'a>(
+    out: &mut String,
+    level: usize,
+    (open, close): (char, char),
+    entries: impl ExactSizeIterator<Item = (Option<&'a String>, &'a OrderedJson)>,
+) -> Result<(), JsonGenerateError> {
+    out.push(open);
+
+    if entries.len() == 0 {
+        out.push(close);
+        return Ok(());
+    }
+
+    for (index, (key, value)) in entries.enumerate() {
+        out.push_str(if index == 0 { "\n" } else { ",\n" });
+        out.push_str(&INDENT.repeat(level + 1));
+
+        if let Some(key) = key {
+            out.push_str(&JsonValue::from(key.clone()).stringify()?);
+            out.push_str(": ");
+        }
+
+        value.write(out, level + 1)?;
+    }
+
+    out.push('\n');
+    out.push_str(&INDENT.repeat(level));
+    out.push(close);
+    Ok(())
+}
+
+/// Builds an object from its pairs, keeping their order.
+#[must_use]
+pub fn object(pairs: Vec<(&str, OrderedJson)>) -> OrderedJson {
+    OrderedJson::Object(
+        pairs
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+impl From<f64> for OrderedJson {
+    fn from(n: f64) -> Self {
+        OrderedJson::Value(n.into())
+    }
+}
+
+impl From<String> for OrderedJson {
+    fn from(s: String) -> Self {
+        OrderedJson::Value(s.into())
+    }
+}
+
+impl From<Vec<OrderedJson>> for OrderedJson {
+    fn from(items: Vec<OrderedJson>) -> Self {
+        OrderedJson::Array(items)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{object, OrderedJson};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn keeps_key_order() {
+        let value = object(vec![
+            ("version", 1.0.into()),
+            ("name", "a \"quoted\" name".to_string().into()),
+            ("empty", OrderedJson::Array(vec![])),
+            (
+                "items",
+                vec![object(vec![("b", 2.0.into()), ("a", 1.0.into())])].into(),
+            ),
+        ]);
+
+        assert_eq!(
+            value.format().unwrap(),
+            "{\n  \"version\": 1,\n  \"name\": \"a \\\"quoted\\\" name\",\n  \"empty\": [],\n  \"items\": [\n    {\n      \"b\": 2,\n      \"a\": 1\n    }\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn formats_like_tinyjson() {
+        // objects with a single key have no order to disagree on.
+        let ordered = object(vec![(
+            "runs",
+            vec![object(vec![("nanos", 1.5.into())]), object(vec![])].into(),
+        )]);
+
+        let json: JsonValue = ordered.format().unwrap().parse().unwrap();
+        assert_eq!(ordered.format().unwrap(), json.format().unwrap());
+    }
+}
@@ -0,0 +1,466 @@
+/// Keeps the debug configurations of VS Code in `.vscode/launch.json` in sync with the scaffolded days.
+/// Each day gets a "Debug day N" and a "Debug day N tests" entry. The file is edited as text, so comments,
+/// formatting and all other entries are left untouched.
+use std::{collections::HashMap, fs, io, path::Path};
+
+use tinyjson::JsonValue;
+
+use crate::template::year::Year;
+use crate::Day;
+
+pub const LAUNCH_FILE: &str = ".vscode/launch.json";
+
+const EMPTY_LAUNCH_FILE: &str = "{\n    \"version\": \"0.2.0\",\n    \"configurations\": []\n}\n";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// What happened to the entries of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Updated,
+    Unchanged,
+}
+
+/// Names of the launch entries of a day, e.g. `Debug day 1` or `Debug 2022 day 1` for other years.
+#[must_use]
+pub fn entry_names(year: Year, day: Day) -> [String; 2] {
+    let name = if year.is_namespaced() {
+        format!("Debug {year} day {}", day.into_inner())
+    } else {
+        format!("Debug day {}", day.into_inner())
+    };
+
+    [name.clone(), format!("{name} tests")]
+}
+
+/// Adds the entries of a day to `.vscode/launch.json`, creating the file if needed.
+/// Existing entries of the day are only replaced if `replace` is set. With `dry_run`, nothing is written.
+pub fn update_file(year: Year, day: Day, replace: bool, dry_run: bool) -> Result<Change, Error> {
+    edit_file(dry_run, |contents| update(contents, year, day, replace))
+}
+
+/// Moves the entries of a day in `.vscode/launch.json` to another year, e.g. when archiving a year.
+/// Returns whether an entry was moved. A missing file is not created.
+pub fn move_day_file(day: Day, from: Year, to: Year, dry_run: bool) -> Result<bool, Error> {
+    edit_file(dry_run, |contents| move_day(contents, day, from, to))
+}
+
+fn edit_file<T>(
+    dry_run: bool,
+    edit: impl FnOnce(&str) -> Result<(String, T), Error>,
+) -> Result<T, Error> {
+    let path = Path::new(LAUNCH_FILE);
+
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => EMPTY_LAUNCH_FILE.to_string(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let (updated, result) = edit(&contents)?;
+
+    if updated != contents && !dry_run {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, updated)?;
+    }
+
+    Ok(result)
+}
+
+/// The names and cargo arguments of the entries of a day.
+fn day_entries(year: Year, day: Day) -> [(String, String); 2] {
+    let bin = year.bin_name(day);
+    let [debug_name, test_name] = entry_names(year, day);
+
+    [
+        (
+            debug_name,
+            format!("[\"build\", \"--bin={bin}\", \"--package=advent_of_code\"]"),
+        ),
+        (
+            test_name,
+            format!("[\"test\", \"--no-run\", \"--bin={bin}\", \"--package=advent_of_code\"]"),
+        ),
+    ]
+}
+
+fn find_entry(
+    contents: &str,
+    configurations: &Configurations,
+    name: &str,
+) -> Option<(usize, usize)> {
+    configurations
+        .entries
+        .iter()
+        .find(|(start, end)| entry_name(&contents[*start..*end]).as_deref() == Some(name))
+        .copied()
+}
+
+/// Adds or replaces the entries of a day in the contents of a launch file.
+pub fn update(
+    contents: &str,
+    year: Year,
+    day: Day,
+    replace: bool,
+) -> Result<(String, Change), Error> {
+    let bin = year.bin_name(day);
+    let mut contents = contents.to_string();
+    let mut changes = vec![];
+
+    for (name, cargo_args) in day_entries(year, day) {
+        let configurations = find_configurations(&contents)?;
+        let indent = configurations.indent(&contents);
+        let entry = format_entry(&name, &cargo_args, &bin, &indent);
+
+        match (
+            find_entry(&contents, &configurations, &name),
+            configurations.entries.last(),
+        ) {
+            (Some(_), _) if !replace => changes.push(Change::Unchanged),
+            (Some((start, end)), _) => {
+                let unchanged = contents[start..end] == entry;
+                contents.replace_range(start..end, &entry);
+                changes.push(if unchanged {
+                    Change::Unchanged
+                } else {
+                    Change::Updated
+                });
+            }
+            (None, Some((_, end))) => {
+                contents.insert_str(*end, &format!(",\n{indent}{entry}"));
+                changes.push(Change::Added);
+            }
+            (None, None) => {
+                let outer = indent.strip_suffix("    ").unwrap_or_default();
+                let (start, end) = (configurations.start + 1, configurations.end);
+                // an empty array may be written as `[]` or span lines, comments inside it are kept.
+                let range = if contents[start..end].trim().is_empty() {
+                    start..end
+                } else {
+                    start..start
+                };
+                contents.replace_range(range, &format!("\n{indent}{entry}\n{outer}"));
+                changes.push(Change::Added);
+            }
+        }
+    }
+
+    let change = if changes.contains(&Change::Added) {
+        Change::Added
+    } else if changes.contains(&Change::Updated) {
+        Change::Updated
+    } else {
+        Change::Unchanged
+    };
+
+    Ok((contents, change))
+}
+
+/// Replaces the entries of a day with the ones of another year. Entries that do not exist are not added.
+pub fn move_day(contents: &str, day: Day, from: Year, to: Year) -> Result<(String, bool), Error> {
+    let bin = to.bin_name(day);
+    let mut contents = contents.to_string();
+    let mut moved = false;
+
+    for ((old_name, _), (name, cargo_args)) in
+        day_entries(from, day).into_iter().zip(day_entries(to, day))
+    {
+        let configurations = find_configurations(&contents)?;
+
+        if let Some((start, end)) = find_entry(&contents, &configurations, &old_name) {
+            let entry = format_entry(&name, &cargo_args, &bin, &configurations.indent(&contents));
+            contents.replace_range(start..end, &entry);
+            moved = true;
+        }
+    }
+
+    Ok((contents, moved))
+}
+
+fn format_entry(name: &str, cargo_args: &str, bin: &str, indent: &str) -> String {
+    let lines = [
+        "{".to_string(),
+        "    \"type\": \"lldb\",".to_string(),
+        "    \"request\": \"launch\",".to_string(),
+        format!("    \"name\": \"{name}\","),
+        "    \"cargo\": {".to_string(),
+        format!("        \"args\": {cargo_args},"),
+        "        \"filter\": {".to_string(),
+        format!("            \"name\": \"{bin}\","),
+        "            \"kind\": \"bin\"".to_string(),
+        "        }".to_string(),
+        "    },".to_string(),
+        "    \"args\": [],".to_string(),
+        "    \"cwd\": \"${workspaceFolder}\"".to_string(),
+        "}".to_string(),
+    ];
+
+    lines.join(&format!("\n{indent}"))
+}
+
+/// The `configurations` array of a launch file.
+struct Configurations {
+    /// Byte offsets of the brackets.
+    start: usize,
+    end: usize,
+    /// Byte ranges of the entries.
+    entries: Vec<(usize, usize)>,
+}
+
+impl Configurations {
+    /// The indentation of the entries, taken from the first one.
+    fn indent(&self, contents: &str) -> String {
+        let Some((start, _)) = self.entries.first() else {
+            return " ".repeat(8);
+        };
+
+        let line_start = contents[..*start].rfind('\n').map_or(0, |x| x + 1);
+        contents[line_start..*start]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect()
+    }
+}
+
+/// Finds the entries of the `configurations` array. Strings and comments are skipped.
+fn find_configurations(contents: &str) -> Result<Configurations, Error> {
+    let bytes = contents.as_bytes();
+    let mut depth = 0;
+    let mut last_key: Option<&str> = None;
+    let mut configurations: Option<Configurations> = None;
+    let mut entry_start = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'"', _) => {
+                let end = skip_string(bytes, i)?;
+                if depth == 1 {
+                    last_key = Some(&contents[i + 1..end - 1]);
+                }
+                i = end;
+                continue;
+            }
+            (b'/', Some(b'/')) => {
+                i = contents[i..].find('\n').map_or(bytes.len(), |x| i + x);
+                continue;
+            }
+            (b'/', Some(b'*')) => {
+                let end = contents[i + 2..]
+                    .find("*/")
+                    .ok_or_else(|| Error::Parser("unterminated comment".into()))?;
+                i += end + 4;
+                continue;
+            }
+            (b'[', _) if depth == 1 && last_key == Some("configurations") => {
+                configurations = Some(Configurations {
+                    start: i,
+                    end: i,
+                    entries: vec![],
+                });
+                depth += 1;
+            }
+            (b'{', _) if depth == 2 && configurations.is_some() => {
+                entry_start = Some(i);
+                depth += 1;
+            }
+            (b'{' | b'[', _) => depth += 1,
+            (b'}', _) if depth == 3 && entry_start.is_some() => {
+                if let (Some(configurations), Some(start)) = (&mut configurations, entry_start) {
+                    configurations.entries.push((start, i + 1));
+                }
+                entry_start = None;
+                depth -= 1;
+            }
+            (b']', _) if depth == 2 => {
+                if let Some(mut configurations) = configurations.take() {
+                    configurations.end = i;
+                    return Ok(configurations);
+                }
+                depth -= 1;
+            }
+            (b'}' | b']', _) => depth -= 1,
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    Err(Error::Parser("no \"configurations\" array found".into()))
+}
+
+/// Returns the offset after the closing quote of the string starting at `start`.
+fn skip_string(bytes: &[u8], start: usize) -> Result<usize, Error> {
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Ok(i + 1),
+            _ => i += 1,
+        }
+    }
+
+    Err(Error::Parser("unterminated string".into()))
+}
+
+/// Reads the name of an entry. Comments and trailing commas, which VS Code allows, are removed before parsing.
+fn entry_name(entry: &str) -> Option<String> {
+    let value: JsonValue = strip_jsonc(entry).parse().ok()?;
+    let fields: &HashMap<String, JsonValue> = value.get()?;
+    let name: &String = fields.get("name")?.get()?;
+    Some(name.clone())
+}
+
+fn strip_jsonc(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'"', _) => {
+                let end = skip_string(bytes, i).map_or(bytes.len(), |x| x);
+                out.push_str(&text[i..end]);
+                i = end;
+            }
+            (b'/', Some(b'/')) => {
+                i = text[i..].find('\n').map_or(bytes.len(), |x| i + x);
+            }
+            (b'/', Some(b'*')) => {
+                i = text[i + 2..].find("*/").map_or(bytes.len(), |x| i + x + 4);
+            }
+            (b',', _) if matches!(text[i + 1..].trim_start().chars().next(), Some('}' | ']')) => {
+                i += 1;
+            }
+            _ => {
+                let len = text[i..].chars().next().map_or(1, char::len_utf8);
+                out.push_str(&text[i..i + len]);
+                i += len;
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{entry_name, move_day, update, Change, EMPTY_LAUNCH_FILE};
+    use crate::day;
+    use crate::template::year::Year;
+
+    const LAUNCH: &str = r#"{
+    // Use IntelliSense to learn about possible attributes.
+    "version": "0.2.0",
+    "configurations": [
+        {
+            "type": "lldb",
+            "request": "launch",
+            "name": "Debug unit tests in library 'advent_of_code'", // a [comment]
+            "args": [],
+            "cwd": "${workspaceFolder}",
+        }
+    ]
+}
+"#;
+
+    fn names(contents: &str) -> Vec<String> {
+        let configurations = super::find_configurations(contents).unwrap();
+        configurations
+            .entries
+            .iter()
+            .map(|(start, end)| entry_name(&contents[*start..*end]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn adds_entries() {
+        let (contents, change) = update(LAUNCH, Year::default(), day!(3), true).unwrap();
+
+        assert_eq!(change, Change::Added);
+        assert_eq!(
+            names(&contents),
+            vec![
+                "Debug unit tests in library 'advent_of_code'",
+                "Debug day 3",
+                "Debug day 3 tests"
+            ]
+        );
+        assert!(contents.contains("// Use IntelliSense"));
+        assert!(
+            contents.contains("\"args\": [\"build\", \"--bin=03\", \"--package=advent_of_code\"],")
+        );
+        assert!(contents.contains("\n        },\n        {\n            \"type\": \"lldb\",\n"));
+    }
+
+    #[test]
+    fn updates_entries() {
+        let (added, _) = update(LAUNCH, Year::default(), day!(3), true).unwrap();
+        let edited = added.replacen("--bin=03\"", "--bin=03\", \"--release\"", 1);
+
+        let (kept, change) = update(&edited, Year::default(), day!(3), false).unwrap();
+        assert_eq!(change, Change::Unchanged);
+        assert_eq!(kept, edited);
+
+        let (updated, change) = update(&edited, Year::default(), day!(3), true).unwrap();
+        assert_eq!(change, Change::Updated);
+        assert_eq!(updated, added);
+
+        let (again, change) = update(&updated, Year::default(), day!(3), true).unwrap();
+        assert_eq!(change, Change::Unchanged);
+        assert_eq!(again, updated);
+    }
+
+    #[test]
+    fn fills_empty_files() {
+        let (contents, change) =
+            update(EMPTY_LAUNCH_FILE, Year::default(), day!(12), true).unwrap();
+
+        assert_eq!(change, Change::Added);
+        assert_eq!(names(&contents), vec!["Debug day 12", "Debug day 12 tests"]);
+        assert!(contents.contains("\"configurations\": [\n        {\n"));
+        assert!(contents.ends_with("}\n    ]\n}\n"));
+    }
+
+    #[test]
+    fn moves_entries_to_other_years() {
+        let (added, _) = update(LAUNCH, Year::default(), day!(3), true).unwrap();
+        let (moved, is_moved) =
+            move_day(&added, day!(3), Year::default(), Year::namespaced(2023)).unwrap();
+
+        assert!(is_moved);
+        assert_eq!(
+            names(&moved),
+            vec![
+                "Debug unit tests in library 'advent_of_code'",
+                "Debug 2023 day 3",
+                "Debug 2023 day 3 tests"
+            ]
+        );
+        assert!(moved.contains("\"--bin=2023-03\""));
+
+        let (unchanged, is_moved) =
+            move_day(LAUNCH, day!(3), Year::default(), Year::namespaced(2023)).unwrap();
+        assert!(!is_moved);
+        assert_eq!(unchanged, LAUNCH);
+    }
+
+    #[test]
+    fn rejects_files_without_configurations() {
+        assert!(update("{ \"version\": \"0.2.0\" }", Year::default(), day!(1), true).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+/// Reads private leaderboards, as served by `/{year}/leaderboard/private/view/{id}.json`, and formats them for the terminal.
+/// Completion times are shown relative to the unlock of each day, like the personal stats of the website.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    io,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{unlock, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{all_days, Day};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous members.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    /// Seconds since the unix epoch, 0 if the member has no stars.
+    pub last_star_ts: u64,
+    /// Seconds since the unix epoch each part of a day was solved at.
+    pub completions: BTreeMap<Day, [Option<u64>; 2]>,
+}
+
+impl Member {
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Stars of a day, from 0 to 2.
+    #[must_use]
+    pub fn stars_of(&self, day: Day) -> usize {
+        self.completions
+            .get(&day)
+            .map_or(0, |parts| parts.iter().flatten().count())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        let value: JsonValue = json.parse().map_err(|e| Error::Parser(format!("{e}")))?;
+        let invalid = || Error::Parser("Malformed leaderboard.".into());
+
+        let fields: &HashMap<String, JsonValue> = value.get().ok_or_else(invalid)?;
+
+        let event: &String = fields
+            .get("event")
+            .and_then(JsonValue::get)
+            .ok_or_else(invalid)?;
+        let year = event.parse().map_err(|_| invalid())?;
+
+        let members: &HashMap<String, JsonValue> = fields
+            .get("members")
+            .and_then(JsonValue::get)
+            .ok_or_else(invalid)?;
+
+        let members = members
+            .values()
+            .map(parse_member)
+            .collect::<Option<Vec<Member>>>()
+            .ok_or_else(invalid)?;
+
+        Ok(Self { year, members })
+    }
+
+    /// Members ordered by local score. Ties go to more stars, then to whoever got their last star first.
+    #[must_use]
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.iter().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+
+    /// Days at least one member has a star for.
+    #[must_use]
+    pub fn started_days(&self) -> Vec<Day> {
+        all_days()
+            .filter(|day| self.members.iter().any(|m| m.stars_of(*day) > 0))
+            .collect()
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_member(value: &JsonValue) -> Option<Member> {
+    let fields: &HashMap<String, JsonValue> = value.get()?;
+
+    let number = |key: &str| -> Option<u64> {
+        match fields.get(key)? {
+            JsonValue::Number(n) => Some(*n as u64),
+            // members without stars have no last star.
+            JsonValue::Null => Some(0),
+            _ => None,
+        }
+    };
+
+    let name = match fields.get("name") {
+        Some(JsonValue::String(name)) => Some(name.clone()),
+        _ => None,
+    };
+
+    let days: &HashMap<String, JsonValue> = fields.get("completion_day_level")?.get()?;
+    let mut completions = BTreeMap::new();
+
+    for (day, parts) in days {
+        let parts: &HashMap<String, JsonValue> = parts.get()?;
+        let mut timestamps = [None, None];
+
+        for (part, timestamp) in ["1", "2"].iter().zip(timestamps.iter_mut()) {
+            if let Some(star) = parts.get(*part) {
+                let star: &HashMap<String, JsonValue> = star.get()?;
+                let ts: &f64 = star.get("get_star_ts")?.get()?;
+                *timestamp = Some(*ts as u64);
+            }
+        }
+
+        completions.insert(day.parse().ok()?, timestamps);
+    }
+
+    Some(Member {
+        id: number("id")?,
+        name,
+        local_score: number("local_score")?,
+        stars: number("stars")?,
+        last_star_ts: number("last_star_ts")?,
+        completions,
+    })
+}
+
+/// Formats the standings with the stars of every member per day.
+/// `★` marks a day with both stars, `☆` a day with only the first one.
+#[must_use]
+pub fn format_standings(leaderboard: &Leaderboard) -> String {
+    let standings = leaderboard.standings();
+    let rank_width = standings.len().to_string().len();
+    let score_width = standings
+        .iter()
+        .map(|m| m.local_score.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let indent = " ".repeat(rank_width + score_width + 4);
+
+    let tens: String = all_days()
+        .map(|day| match day.into_inner() / 10 {
+            0 => ' ',
+            x => char::from(b'0' + x),
+        })
+        .collect();
+    let ones: String = all_days()
+        .map(|day| char::from(b'0' + day.into_inner() % 10))
+        .collect();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{indent}{tens}");
+    let _ = writeln!(out, "{indent}{ones}");
+
+    for (index, member) in standings.iter().enumerate() {
+        let stars: String = all_days()
+            .map(|day| match member.stars_of(day) {
+                2 => '★',
+                1 => '☆',
+                _ => '·',
+            })
+            .collect();
+
+        let _ = writeln!(
+            out,
+            "{:>rank_width$}) {ANSI_BOLD}{:>score_width$}{ANSI_RESET}  {stars}  {} {ANSI_ITALIC}({} ⭐️){ANSI_RESET}",
+            index + 1,
+            member.local_score,
+            member.display_name(),
+            member.stars,
+        );
+    }
+
+    out
+}
+
+/// Formats the completion times of a day's parts, relative to the unlock of the day.
+/// `None` if no member has a star for the day.
+#[must_use]
+pub fn format_day(leaderboard: &Leaderboard, day: Day) -> Option<String> {
+    let unlock = unlock::unlock_time(leaderboard.year, day)
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+
+    let mut solved: Vec<(&Member, [Option<u64>; 2])> = leaderboard
+        .members
+        .iter()
+        .filter_map(|m| Some((m, *m.completions.get(&day)?)))
+        .filter(|(_, parts)| parts.iter().any(Option::is_some))
+        .collect();
+
+    if solved.is_empty() {
+        return None;
+    }
+
+    // members with both stars come first, ordered by when they got them.
+    solved.sort_by_key(|(m, [one, two])| (two.is_none(), *two, *one, m.id));
+
+    let name_width = solved
+        .iter()
+        .map(|(m, _)| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let format_part = |timestamp: Option<u64>| match timestamp {
+        Some(ts) => unlock::format_remaining(Duration::from_secs(ts.saturating_sub(unlock))),
+        None => "-".into(),
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(time since unlock){ANSI_RESET}"
+    );
+
+    for (member, [one, two]) in solved {
+        let _ = writeln!(
+            out,
+            "  {:<name_width$}  Part 1: {:>15}  Part 2: {:>15}",
+            member.display_name(),
+            format_part(one),
+            format_part(two),
+        );
+    }
+
+    Some(out)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_day, format_standings, Leaderboard};
+    use crate::day;
+
+    // 2023-12-01T05:00:00Z is 1701406800.
+    const LEADERBOARD: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 5, "global_score": 0,
+                "last_star_ts": 1701493800,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1701407100, "star_index": 0 },
+                        "2": { "get_star_ts": 1701408000, "star_index": 1 }
+                    },
+                    "2": { "1": { "get_star_ts": 1701493800, "star_index": 2 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 5, "global_score": 0,
+                "last_star_ts": 1701410400,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1701406860, "star_index": 0 },
+                        "2": { "get_star_ts": 1701410400, "star_index": 1 }
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        assert_eq!(leaderboard.year, 2023);
+
+        let standings: Vec<String> = leaderboard
+            .standings()
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+
+        // alice and the anonymous member tie on score, alice has more stars.
+        assert_eq!(standings, vec!["alice", "(anonymous user #2)", "carol"]);
+        assert_eq!(leaderboard.started_days(), vec![day!(1), day!(2)]);
+
+        assert!(Leaderboard::parse(r#"{"event": "2023"}"#).is_err());
+    }
+
+    #[test]
+    fn formats_standings() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let lines: Vec<String> = format_standings(&leaderboard)
+            .lines()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[2].starts_with("1) \u{1b}[1m5\u{1b}[0m  ★☆·"));
+        assert!(lines[4].contains("·························  carol"));
+    }
+
+    #[test]
+    fn formats_completion_times() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let breakdown = format_day(&leaderboard, day!(1)).unwrap();
+        let lines: Vec<&str> = breakdown.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains("alice") && lines[1].contains("00h 05m 00s"));
+        assert!(lines[1].contains("00h 20m 00s"));
+        assert!(lines[2].contains("(anonymous user #2)") && lines[2].contains("01h 00m 00s"));
+
+        let breakdown = format_day(&leaderboard, day!(2)).unwrap();
+        assert!(breakdown.contains("00h 10m 00s"));
+        assert!(breakdown.lines().nth(1).unwrap().ends_with('-'));
+
+        assert_eq!(format_day(&leaderboard, day!(3)), None);
+    }
+}
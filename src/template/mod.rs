@@ -1,37 +1,67 @@
 use crate::Day;
 use std::{env, fs};
+use year::Year;
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
+pub mod bench_config;
+pub mod benchmark_history;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod integrity;
+pub mod journal;
+pub mod json;
+pub mod launch_config;
+pub mod leaderboard;
+pub mod module_template;
 pub mod readme_benchmarks;
+pub mod report;
 pub mod runner;
+pub mod stats;
+#[cfg(feature = "test_lib")]
+pub mod test_helpers;
+pub mod timeout;
+pub mod unlock;
+pub mod verdict;
+pub mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Files are looked up in the data folder of the year the running solution belongs to.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_data_file(folder, &format!("{day}.txt"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_data_file(folder, &format!("{day}-{part}.txt"))
+}
+
+fn read_data_file(folder: &str, file: &str) -> String {
+    let path = Year::of_binary().data_path(folder, file);
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = fs::read_to_string(cwd.join(&path));
+    let contents = f.expect("could not open input file");
+
+    // e.g. an example that was not filled in after scaffolding.
+    if let Err(problem) = integrity::check(&contents) {
+        eprintln!("⚠ \"{}\" is {problem}.", path.display());
+    }
+
+    contents
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// An optional parse function can be passed as second argument, e.g. `solution!(5, parse_input)`.
+/// It is run (and timed) once, and a reference to its output is passed to both parts instead of the raw input.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -40,9 +70,27 @@ macro_rules! solution {
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input = advent_of_code::template::input::read_input(DAY);
+            let outcomes = [
+                run_part(part_one, &input, DAY, 1),
+                run_part(part_two, &input, DAY, 2),
+            ];
+            exit_with(&outcomes);
+        }
+    };
+    ($day:expr, $parse:expr) => {
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let input = advent_of_code::template::input::read_input(DAY);
+            let parsed = run_parse($parse, input.as_str());
+            let outcomes = [
+                run_part(part_one, &parsed, DAY, 1),
+                run_part(part_two, &parsed, DAY, 2),
+            ];
+            exit_with(&outcomes);
         }
     };
 }
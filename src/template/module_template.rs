@@ -0,0 +1,247 @@
+/// Renders the module file of a scaffolded day.
+/// Projects can provide their own templates in `templates/{name}.rs` and select them with `--template <name>`.
+/// `templates/default.rs` replaces the built-in template. Templates can use these placeholders:
+/// `{{day}}`, `{{year}}`, `{{title}}`, `{{example_part_one}}` and `{{example_part_two}}`.
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use crate::template::{answers::Answers, aoc_client::get_puzzle_path, examples, year::Year};
+use crate::Day;
+
+const TEMPLATES_FOLDER: &str = "templates";
+const DEFAULT_TEMPLATE_NAME: &str = "default";
+
+const DEFAULT_TEMPLATE: &str = r#"advent_of_code::solution!({{day}});
+
+pub fn part_one(input: &str) -> Option<u32> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, {{example_part_one}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, {{example_part_two}});
+    }
+}
+"#;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The template does not exist. Holds the available templates.
+    NotFound(String, Vec<String>),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound(name, available) if available.is_empty() => write!(
+                f,
+                "template \"{name}\" not found, \"{TEMPLATES_FOLDER}/\" has no templates."
+            ),
+            Error::NotFound(name, available) => write!(
+                f,
+                "template \"{name}\" not found, available templates: {}.",
+                available.join(", ")
+            ),
+            Error::IO(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The values placeholders are replaced with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholders {
+    pub day: Day,
+    pub year: Option<u16>,
+    /// The title of the puzzle, e.g. `Trebuchet?!`.
+    pub title: Option<String>,
+    pub example_answers: Answers,
+}
+
+impl Placeholders {
+    /// Collects the values of a day from its downloaded puzzle and extracted example answers, if any.
+    #[must_use]
+    pub fn of_day(year: Year, day: Day) -> Self {
+        let title = fs::read_to_string(get_puzzle_path(year, day))
+            .ok()
+            .and_then(|puzzle| parse_title(&puzzle));
+
+        let example_answers = fs::read_to_string(examples::get_answers_path(year, day))
+            .ok()
+            .and_then(|contents| Answers::parse(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            day,
+            year: year.number(),
+            title,
+            example_answers,
+        }
+    }
+}
+
+/// Loads a template by name. Without a name, `templates/default.rs` is used if it exists, the built-in template otherwise.
+pub fn load(name: Option<&str>) -> Result<String, Error> {
+    let path = get_path(name.unwrap_or(DEFAULT_TEMPLATE_NAME));
+
+    match (name, path) {
+        (_, Some(path)) if path.exists() => Ok(fs::read_to_string(path)?),
+        (None, _) => Ok(DEFAULT_TEMPLATE.to_string()),
+        (Some(name), _) => Err(Error::NotFound(name.to_string(), list())),
+    }
+}
+
+/// Lists the templates of the project.
+#[must_use]
+pub fn list() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(TEMPLATES_FOLDER) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|x| x == "rs"))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+        .filter(|name| is_valid_name(name))
+        .collect();
+
+    names.sort();
+    names
+}
+
+/// Template names become file names, so they are limited to letters, digits, `-` and `_`.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn get_path(name: &str) -> Option<PathBuf> {
+    is_valid_name(name).then(|| PathBuf::from(TEMPLATES_FOLDER).join(format!("{name}.rs")))
+}
+
+/// Replaces the placeholders of a template.
+/// Example answers become expressions a test can compare a result with, e.g. `Some(142)`, or `None` if they are unknown.
+#[must_use]
+pub fn render(template: &str, placeholders: &Placeholders) -> String {
+    let day = placeholders.day;
+
+    template
+        .replace("{{day}}", &day.into_inner().to_string())
+        .replace(
+            "{{year}}",
+            &placeholders.year.map(|x| x.to_string()).unwrap_or_default(),
+        )
+        .replace(
+            "{{title}}",
+            &placeholders
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("Day {day}")),
+        )
+        .replace(
+            "{{example_part_one}}",
+            &answer_expression(placeholders.example_answers.part_one.as_deref()),
+        )
+        .replace(
+            "{{example_part_two}}",
+            &answer_expression(placeholders.example_answers.part_two.as_deref()),
+        )
+}
+
+fn answer_expression(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) if answer.parse::<i128>().is_ok() => format!("Some({answer})"),
+        Some(answer) => format!("Some({answer:?}.to_string())"),
+        None => "None".into(),
+    }
+}
+
+/// Reads the title of a puzzle from its heading, e.g. `## --- Day 1: Trebuchet?! ---`.
+fn parse_title(puzzle: &str) -> Option<String> {
+    let heading = puzzle
+        .lines()
+        .find(|line| line.starts_with("## --- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    let title = title.trim_end_matches('-').trim();
+
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_title, render, Placeholders, DEFAULT_TEMPLATE};
+    use crate::day;
+    use crate::template::answers::Answers;
+
+    fn placeholders(part_one: Option<&str>, part_two: Option<&str>) -> Placeholders {
+        Placeholders {
+            day: day!(7),
+            year: Some(2023),
+            title: Some("Camel Cards".into()),
+            example_answers: Answers {
+                part_one: part_one.map(String::from),
+                part_two: part_two.map(String::from),
+            },
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let template =
+            "// {{year}} day {{day}}: {{title}}\n{{example_part_one}} {{example_part_two}}";
+
+        assert_eq!(
+            render(template, &placeholders(Some("6440"), Some("J5"))),
+            "// 2023 day 7: Camel Cards\nSome(6440) Some(\"J5\".to_string())"
+        );
+
+        let mut unknown = placeholders(None, None);
+        unknown.title = None;
+        assert_eq!(
+            render(template, &unknown),
+            "// 2023 day 7: Day 07\nNone None"
+        );
+    }
+
+    #[test]
+    fn renders_default_template() {
+        let module = render(DEFAULT_TEMPLATE, &placeholders(Some("6440"), None));
+
+        assert!(module.starts_with("advent_of_code::solution!(7);"));
+        assert!(module.contains("assert_eq!(result, Some(6440));"));
+        assert!(module.contains("assert_eq!(result, None);"));
+        assert!(!module.contains("{{"));
+    }
+
+    #[test]
+    fn parses_titles() {
+        assert_eq!(
+            parse_title("## --- Day 1: Trebuchet?! ---\n\nSomething is wrong"),
+            Some("Trebuchet?!".into())
+        );
+        assert_eq!(parse_title("## --- Part Two ---"), None);
+    }
+}
@@ -2,6 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::{runner::PARSE_LABEL, stats::BenchStats};
 use crate::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -21,8 +22,14 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
+    /// Timing of the shared parse step, for solutions that declare one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Raw statistics of each step, present whenever the step ran.
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
     pub total_nanos: f64,
 }
 
@@ -61,32 +68,86 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // the parse column is only shown if at least one solution declares a parse step.
+    let has_parse = timings.iter().any(|t| t.parse.is_some());
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_parse {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
+
+    let mut details: Vec<String> = vec![];
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
+
+        let parse_cell = if has_parse {
+            format!(" `{}` |", timing.parse.unwrap_or_else(|| "-".into()))
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{} `{}` | `{}` |",
             timing.day.into_inner(),
             path,
+            parse_cell,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
+
+        for (part, stats) in [
+            (PARSE_LABEL, timing.parse_stats),
+            ("1", timing.part_1_stats),
+            ("2", timing.part_2_stats),
+        ] {
+            // the detailed statistics are only meaningful if the part was benched.
+            if let Some(stats) = stats.filter(|s| s.samples > 1) {
+                details.push(construct_stats_row(timing.day, part, &stats));
+            }
+        }
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if !details.is_empty() {
+        lines.push(String::new());
+        lines.push("<details><summary>Statistics</summary>".into());
+        lines.push(String::new());
+        lines.push("| Day | Part | Median | Min | Max | σ | p95 | p99 | Samples |".into());
+        lines.push(
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: |".into(),
+        );
+        lines.append(&mut details);
+        lines.push(String::new());
+        lines.push("</details>".into());
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
+fn construct_stats_row(day: Day, part: &str, stats: &BenchStats) -> String {
+    format!(
+        "| {} | {part} | `{:.1?}` | `{:.1?}` | `{:.1?}` | `{:.1?}` | `{:.1?}` | `{:.1?}` | {} |",
+        day.into_inner(),
+        stats.median,
+        stats.min,
+        stats.max,
+        stats.std_dev,
+        stats.p95,
+        stats.p99,
+        stats.samples
+    )
+}
+
 fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
@@ -104,27 +165,41 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::time::Duration;
+
     use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use crate::{day, template::stats::BenchStats};
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
+                parse: None,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
+                parse: None,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -183,4 +258,37 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmark_stats() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[0].part_1_stats = Some(BenchStats {
+            samples: 100,
+            outliers: 2,
+            mean: Duration::from_millis(10),
+            median: Duration::from_millis(9),
+            min: Duration::from_millis(8),
+            max: Duration::from_millis(12),
+            std_dev: Duration::from_micros(500),
+            p95: Duration::from_millis(11),
+            p99: Duration::from_millis(12),
+        });
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Part | Median | Min | Max | σ | p95 | p99 | Samples |"));
+        assert!(s.contains(
+            "| 1 | 1 | `9.0ms` | `8.0ms` | `12.0ms` | `500.0µs` | `11.0ms` | `12.0ms` | 100 |"
+        ));
+    }
+
+    #[test]
+    fn format_parse_column() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[1].parse = Some("5ms".into());
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
 }
@@ -0,0 +1,301 @@
+/// Machine-readable output of solution binaries.
+/// When a solution is invoked with `--format json`, the runner prints one JSON object per line for each part instead of
+/// the human-readable output, e.g. `{"part":1,"answer":"42","status":"ok","nanos":1200,"samples":10,...}`.
+/// Lines that are not valid records (e.g. debug output of a solution) are left alone by consumers.
+use std::{collections::HashMap, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{alloc::AllocStats, stats::BenchStats};
+
+pub const FORMAT_ARG: &str = "--format";
+pub const JSON_FORMAT: &str = "json";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// The solution returned `None`.
+    Unsolved,
+    /// The part exceeded its time limit and was stopped.
+    TimedOut,
+    /// The part panicked. Holds the panic message.
+    Panicked(String),
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Unsolved => "unsolved",
+            Status::TimedOut => "timed_out",
+            Status::Panicked(_) => "panicked",
+        }
+    }
+
+    fn from_str(s: &str, message: Option<&String>) -> Option<Self> {
+        match s {
+            "ok" => Some(Status::Ok),
+            "unsolved" => Some(Status::Unsolved),
+            "timed_out" => Some(Status::TimedOut),
+            "panicked" => Some(Status::Panicked(message?.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of running one part of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    /// The part number. `0` denotes the shared parse step.
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The accepted answer from `data/answers/`, if known.
+    pub expected: Option<String>,
+    pub status: Status,
+    pub stats: BenchStats,
+    /// Heap usage of a single execution, if the `alloc-stats` feature is enabled.
+    pub alloc: Option<AllocStats>,
+}
+
+impl PartReport {
+    /// Creates the report of a part that was stopped after `limit`.
+    #[must_use]
+    pub fn timed_out(part: u8, limit: Duration) -> Self {
+        Self {
+            part,
+            answer: None,
+            expected: None,
+            status: Status::TimedOut,
+            stats: BenchStats::single(limit),
+            alloc: None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_parse_step(&self) -> bool {
+        self.part == 0
+    }
+
+    /// Compares the answer with the accepted one. `None` if no answer is known.
+    #[must_use]
+    pub fn is_correct(&self) -> Option<bool> {
+        let expected = self.expected.as_ref()?;
+        Some(self.answer.as_ref() == Some(expected))
+    }
+
+    /// Serializes the report to a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut object: HashMap<String, JsonValue> = HashMap::new();
+
+        let mut insert = |key: &str, value: JsonValue| {
+            object.insert(key.into(), value);
+        };
+
+        insert("part", f64::from(self.part).into());
+        insert(
+            "answer",
+            self.answer.clone().map_or(JsonValue::Null, JsonValue::from),
+        );
+        insert(
+            "expected",
+            self.expected
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        insert("status", self.status.as_str().to_string().into());
+
+        if let Status::Panicked(message) = &self.status {
+            insert("message", message.clone().into());
+        }
+
+        insert("nanos", nanos(self.stats.mean));
+        insert("samples", (self.stats.samples as f64).into());
+        insert("outliers", (self.stats.outliers as f64).into());
+        insert("median_nanos", nanos(self.stats.median));
+        insert("min_nanos", nanos(self.stats.min));
+        insert("max_nanos", nanos(self.stats.max));
+        insert("std_dev_nanos", nanos(self.stats.std_dev));
+        insert("p95_nanos", nanos(self.stats.p95));
+        insert("p99_nanos", nanos(self.stats.p99));
+
+        if let Some(alloc) = self.alloc {
+            insert("allocations", (alloc.allocations as f64).into());
+            insert("allocated_bytes", (alloc.allocated_bytes as f64).into());
+            insert("peak_bytes", (alloc.peak_bytes as f64).into());
+        }
+
+        // serializing a map of strings and finite numbers can not fail.
+        JsonValue::from(object).stringify().unwrap()
+    }
+
+    /// Parses a line printed by [`PartReport::to_json`]. Returns `None` for lines that are not records.
+    #[must_use]
+    pub fn from_json(line: &str) -> Option<Self> {
+        let line = line.trim();
+
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        let value: JsonValue = line.parse().ok()?;
+        let object: &HashMap<String, JsonValue> = value.get()?;
+
+        let number = |key: &str| -> Option<f64> { object.get(key)?.get::<f64>().copied() };
+        let duration = |key: &str| number(key).map(to_duration);
+
+        let optional_string = |key: &str| match object.get(key) {
+            None | Some(JsonValue::Null) => Some(None),
+            Some(JsonValue::String(s)) => Some(Some(s.clone())),
+            _ => None,
+        };
+
+        let answer = match object.get("answer")? {
+            JsonValue::Null => None,
+            JsonValue::String(s) => Some(s.clone()),
+            _ => return None,
+        };
+
+        let expected = optional_string("expected")?;
+
+        let status: &String = object.get("status")?.get()?;
+        let message: Option<&String> = object.get("message").and_then(JsonValue::get);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let alloc = match number("allocations") {
+            Some(allocations) => Some(AllocStats {
+                allocations: allocations as usize,
+                allocated_bytes: number("allocated_bytes")? as usize,
+                peak_bytes: number("peak_bytes")? as usize,
+            }),
+            None => None,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(Self {
+            part: number("part")? as u8,
+            answer,
+            expected,
+            status: Status::from_str(status, message)?,
+            stats: BenchStats {
+                samples: number("samples")? as u128,
+                outliers: number("outliers")? as u128,
+                mean: duration("nanos")?,
+                median: duration("median_nanos")?,
+                min: duration("min_nanos")?,
+                max: duration("max_nanos")?,
+                std_dev: duration("std_dev_nanos")?,
+                p95: duration("p95_nanos")?,
+                p99: duration("p99_nanos")?,
+            },
+            alloc,
+        })
+    }
+}
+
+/// Checks whether a solution binary was asked to emit JSON records.
+#[must_use]
+pub fn is_json_format(args: &[String]) -> bool {
+    args.windows(2)
+        .any(|w| w[0] == FORMAT_ARG && w[1] == JSON_FORMAT)
+}
+
+fn nanos(duration: Duration) -> JsonValue {
+    (duration.as_nanos() as f64).into()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_json_format, PartReport, Status};
+    use crate::template::{alloc::AllocStats, stats::BenchStats};
+    use std::time::Duration;
+
+    #[test]
+    fn roundtrips_report() {
+        let report = PartReport {
+            part: 2,
+            answer: Some("(1, 2) @ 3 samples)\nline two".into()),
+            expected: Some("42".into()),
+            status: Status::Ok,
+            stats: BenchStats::from_samples(&[
+                Duration::from_nanos(100),
+                Duration::from_nanos(200),
+                Duration::from_nanos(300),
+            ])
+            .unwrap(),
+            alloc: Some(AllocStats {
+                allocations: 12,
+                allocated_bytes: 4096,
+                peak_bytes: 1024,
+            }),
+        };
+
+        let json = report.to_json();
+        assert!(!json.contains('\n'));
+        assert_eq!(PartReport::from_json(&json), Some(report));
+    }
+
+    #[test]
+    fn roundtrips_unsolved_report() {
+        let report = PartReport {
+            part: 1,
+            answer: None,
+            expected: None,
+            status: Status::Unsolved,
+            stats: BenchStats::single(Duration::from_millis(12)),
+            alloc: None,
+        };
+        assert_eq!(PartReport::from_json(&report.to_json()), Some(report));
+    }
+
+    #[test]
+    fn roundtrips_timed_out_report() {
+        let report = PartReport::timed_out(2, Duration::from_secs(10));
+        assert_eq!(PartReport::from_json(&report.to_json()), Some(report));
+    }
+
+    #[test]
+    fn roundtrips_panicked_report() {
+        let report = PartReport {
+            part: 1,
+            answer: None,
+            expected: None,
+            status: Status::Panicked("index out of bounds (src/bin/01.rs:3:5)".into()),
+            stats: BenchStats::single(Duration::from_micros(3)),
+            alloc: None,
+        };
+        assert_eq!(PartReport::from_json(&report.to_json()), Some(report));
+    }
+
+    #[test]
+    fn checks_answer() {
+        let mut report = PartReport::timed_out(1, Duration::from_secs(1));
+        assert_eq!(report.is_correct(), None);
+
+        report.expected = Some("42".into());
+        assert_eq!(report.is_correct(), Some(false));
+
+        report.answer = Some("42".into());
+        assert_eq!(report.is_correct(), Some(true));
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(PartReport::from_json("Part 1: 42 (1.0ms)"), None);
+        assert_eq!(PartReport::from_json("{\"debug\": true}"), None);
+        assert_eq!(PartReport::from_json("{ not json"), None);
+    }
+
+    #[test]
+    fn detects_json_format() {
+        let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+        assert!(is_json_format(&args("01 --time --format json")));
+        assert!(!is_json_format(&args("01 --time")));
+        assert!(!is_json_format(&args("01 --format")));
+    }
+}
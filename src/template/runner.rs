@@ -1,63 +1,267 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    alloc::{self, AllocStats},
+    answers, aoc_client,
+    bench_config::{BenchConfig, BenchOptions},
+    input::{InputSource, DEFAULT_INPUT_SET},
+    journal::{self, Journal, Refusal, Submission},
+    report::{is_json_format, PartReport, Status},
+    stats::BenchStats,
+    timeout::{self, TimeoutOptions, Watchdog, TIMEOUT_EXIT_CODE},
+    verdict::{Verdict, WRONG_ANSWER_EXIT_CODE},
+    year::Year,
+    ANSI_ITALIC, ANSI_RESET,
+};
 use crate::Day;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::thread;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// What a part leaves behind for the exit code of the solution binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartOutcome {
+    pub panicked: bool,
+    /// The verdict of a submitted answer, or why the journal refused to submit it.
+    pub submission: Option<Result<Verdict, Refusal>>,
+}
+
+/// Runs a part of a solution and reports its result.
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) -> PartOutcome {
     let part_str = format!("Part {part}");
+    let args: Vec<String> = env::args().collect();
+    let is_json = is_json_format(&args);
+    // recorded answers belong to the puzzle input or input set, submissions to the puzzle input only.
+    let source = InputSource::from_args(&args).ok().flatten();
+    let is_custom_input = source.is_some();
+
+    let (result, stats, alloc) = run_timed(func, input, part, |result| {
+        if !is_json {
+            print!("{}", format_result(result, &part_str, ""));
+        }
+    });
+
+    let (answer, status) = match &result {
+        Ok(Some(result)) => (Some(result.to_string()), Status::Ok),
+        Ok(None) => (None, Status::Unsolved),
+        Err(message) => (None, Status::Panicked(message.clone())),
+    };
+
+    let report = PartReport {
+        part,
+        answer,
+        expected: match &source {
+            None => load_expected(DEFAULT_INPUT_SET, day, part),
+            Some(InputSource::Set(set)) => load_expected(set, day, part),
+            Some(_) => None,
+        },
+        status,
+        stats,
+        alloc,
+    };
+
+    emit_report(&report, is_json);
+
+    let mut outcome = PartOutcome {
+        panicked: matches!(report.status, Status::Panicked(_)),
+        submission: None,
+    };
+
+    let Ok(Some(result)) = result else {
+        return outcome;
+    };
+
+    if is_custom_input {
+        if args.iter().any(|x| x == "--submit") {
+            eprintln!("Not submitting {part_str}, the solution ran against a custom input.");
+        }
+        return outcome;
+    }
+
+    outcome.submission = submit_result(result, day, part);
+    outcome
+}
+
+/// Exits with the code of a rejected or rate-limited submission, or with the panic exit code if a part panicked.
+/// Called once all parts have run, so a failing part does not keep the other one from running.
+pub fn exit_with(outcomes: &[PartOutcome]) {
+    if let Some(code) = exit_code(outcomes) {
+        let _ = stdout().flush();
+        process::exit(code);
+    }
+}
+
+fn exit_code(outcomes: &[PartOutcome]) -> Option<i32> {
+    let verdict_code = outcomes
+        .iter()
+        .filter_map(|outcome| outcome.submission.as_ref())
+        .map(|submission| match submission {
+            Ok(verdict) => verdict.exit_code(),
+            // a refused answer is known to be wrong.
+            Err(_) => WRONG_ANSWER_EXIT_CODE,
+        })
+        .find(|code| *code != 0);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    verdict_code.or_else(|| {
+        outcomes
+            .iter()
+            .any(|outcome| outcome.panicked)
+            .then_some(PANIC_EXIT_CODE)
+    })
+}
+
+/// Run the shared parse step of a solution. Its output is passed to both parts, so parsing is timed separately from solving.
+/// If parsing panics, neither part can run and the process exits after reporting the panic.
+pub fn run_parse<I: Clone, P>(func: impl Fn(I) -> P, input: I) -> P {
+    let is_json = is_json_format(&env::args().collect::<Vec<_>>());
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (parsed, stats, alloc) = run_timed(func, input, 0, |_| {
+        if !is_json {
+            print!("{PARSE_LABEL}:");
+        }
+    });
+
+    let report = PartReport {
+        part: 0,
+        answer: None,
+        expected: None,
+        status: match &parsed {
+            Ok(_) => Status::Ok,
+            Err(message) => Status::Panicked(message.clone()),
+        },
+        stats,
+        alloc,
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    emit_report(&report, is_json);
+
+    parsed.unwrap_or_else(|_| process::exit(PANIC_EXIT_CODE))
+}
+
+fn emit_report(report: &PartReport, is_json: bool) {
+    if is_json {
+        println!("{}", report.to_json());
+    } else {
+        print_report(report);
     }
 }
 
+/// Prints the human-readable result of a part.
+pub fn print_report(report: &PartReport) {
+    print!("{}", format_report(report));
+}
+
+/// Formats the human-readable result of a part, as printed by [`print_report`].
+#[must_use]
+pub fn format_report(report: &PartReport) -> String {
+    let mut duration_str = format_check(report);
+    duration_str.push_str(&format_duration(&report.stats));
+
+    if let Some(alloc) = &report.alloc {
+        duration_str.push_str(&format_alloc(alloc));
+    }
+
+    let label = match report.part {
+        0 => PARSE_LABEL.to_string(),
+        n => format!("Part {n}"),
+    };
+
+    let mut output = match &report.status {
+        Status::TimedOut => {
+            return format!("\r{label}: ⏱ timed out after {:.1?}\n", report.stats.mean);
+        }
+        Status::Panicked(message) => return format!("\r{label}: ✖ panicked: {message}\n"),
+        Status::Ok | Status::Unsolved if report.is_parse_step() => {
+            format!("\r{label}:{duration_str}\n")
+        }
+        Status::Ok | Status::Unsolved => format_result(&report.answer, &label, &duration_str),
+    };
+
+    if report.status == Status::Ok && report.stats.samples > 1 {
+        output.push_str(&format_stats(&report.stats));
+        output.push('\n');
+    }
+
+    output
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. with `--time`, the function is benched (by default approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Heap usage is measured for the first execution only.
+/// If a time limit is configured, a watchdog stops the process once the part, including its benchmark samples, or the whole run exceeds it.
+/// A panic in the first execution is returned as its message, the part is not benched then.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    part: u8,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+) -> (Result<T, String>, BenchStats, Option<AllocStats>) {
+    let args: Vec<String> = env::args().collect();
+
+    // the part limit covers the benchmark samples as well, so a slow part can not hang `--time`.
+    let _watchdog = arm_watchdogs(&args, part);
+
+    let ((result, base_time), alloc_stats) = alloc::measure(|| {
+        let timer = Instant::now();
+        let result = catch_panic(|| func(input.clone()));
+        (result, timer.elapsed())
+    });
+
+    let result = match result {
+        Ok(result) => result,
+        Err(message) => return (Err(message), BenchStats::single(base_time), alloc_stats),
+    };
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if args.iter().any(|x| x == "--time") {
+        let config = match BenchOptions::from_args(&args) {
+            Ok(options) => options.resolve(),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        if !is_json_format(&args) {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
+
+        bench(func, input, &base_time, &config)
     } else {
-        (base_time, 1)
+        BenchStats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (Ok(result), stats, alloc_stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Bench a solution part. Warmup runs are not recorded, so caches and branch predictors are primed before measuring.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> BenchStats {
+    let bench_iterations = config.sample_count(base_time);
+    let warmup_iterations = config.warmup_count(bench_iterations);
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    for _ in 0..warmup_iterations {
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,30 +273,144 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers).unwrap_or_else(|| BenchStats::single(*base_time))
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Runs `func` and captures a panic as its message and location instead of unwinding into the caller.
+/// The default panic output is suppressed meanwhile, the message is reported as status of the part instead.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static MESSAGE: Mutex<Option<String>> = Mutex::new(None);
+
+    let previous_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(|info| {
+        let payload = info.payload();
+
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".into());
+
+        let message = match info.location() {
+            Some(location) => format!("{message} ({location})"),
+            None => message,
+        };
+
+        if let Ok(mut stored) = MESSAGE.lock() {
+            *stored = Some(message);
+        }
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+
+    panic::set_hook(previous_hook);
+
+    result.map_err(|_| {
+        MESSAGE
+            .lock()
+            .ok()
+            .and_then(|mut stored| stored.take())
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+/// The part that is currently running, reported by the day watchdog when it fires.
+static CURRENT_PART: AtomicU8 = AtomicU8::new(0);
+
+/// Arms the day watchdog on the first call and returns a watchdog for the run of `part`, including its benchmark samples.
+fn arm_watchdogs(args: &[String], part: u8) -> Option<Watchdog> {
+    let options = match TimeoutOptions::from_args(args) {
+        Ok(options) => options.resolve(),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let is_json = is_json_format(args);
+
+    CURRENT_PART.store(part, Ordering::SeqCst);
+
+    if let Some(limit) = options.day_limit() {
+        timeout::arm_for_process(limit, move |limit| {
+            exit_timed_out(CURRENT_PART.load(Ordering::SeqCst), limit, is_json);
+        });
+    }
+
+    options
+        .part_limit()
+        .map(|limit| Watchdog::start(limit, move |limit| exit_timed_out(part, limit, is_json)))
+}
+
+fn exit_timed_out(part: u8, limit: Duration, is_json: bool) {
+    emit_report(&PartReport::timed_out(part, limit), is_json);
+    let _ = stdout().flush();
+    process::exit(TIMEOUT_EXIT_CODE);
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// Reads the accepted answer of a part from the `answers` folder of the solution's year.
+fn load_expected(set: &str, day: Day, part: u8) -> Option<String> {
+    let year = Year::of_binary();
+
+    match answers::load(year, set, day) {
+        Ok(answers) => answers.get(part).cloned(),
+        Err(e) => {
+            eprintln!(
+                "Failed to read answers from {}: {e:?}",
+                answers::get_path(year, set, day).display()
+            );
+            None
+        }
+    }
+}
+
+/// Marks an answer as correct or wrong, if the accepted answer is known.
+fn format_check(report: &PartReport) -> String {
+    match (report.is_correct(), &report.expected) {
+        (Some(true), _) => " ✔".into(),
+        (Some(false), Some(expected)) if !expected.contains('\n') => {
+            format!(" ✘ expected {expected}")
+        }
+        (Some(false), _) => " ✘".into(),
+        (None, _) => String::new(),
+    }
+}
+
+fn format_duration(stats: &BenchStats) -> String {
+    let BenchStats { mean, samples, .. } = stats;
+    if *samples == 1 {
+        format!(" ({mean:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({mean:.1?} @ {samples} samples)")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_alloc(alloc: &AllocStats) -> String {
+    format!(
+        " [{} allocs · {} total · {} peak]",
+        alloc.allocations,
+        alloc::format_bytes(alloc.allocated_bytes),
+        alloc::format_bytes(alloc.peak_bytes)
+    )
+}
+
+/// Formats the detailed report of a benched part.
+fn format_stats(stats: &BenchStats) -> String {
+    format!(
+        "{STATS_PREFIX}median {:.1?} · min {:.1?} · max {:.1?} · σ {:.1?} · p95 {:.1?} · p99 {:.1?} · {} outliers",
+        stats.median, stats.min, stats.max, stats.std_dev, stats.p95, stats.p99, stats.outliers
+    )
+}
+
+const STATS_PREFIX: &str = "        └ ";
+/// Exit code of a solution whose parse step panicked, or one of whose parts panicked once both parts have run.
+/// Matches the exit code of an uncaught panic.
+const PANIC_EXIT_CODE: i32 = 101;
+pub(crate) const PARSE_LABEL: &str = "Parse";
+
+/// Formats the result of a part. Without a duration, it is an intermediate result that is completed by a later line.
+fn format_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) -> String {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -100,41 +418,33 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    str
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    format!("\r{str}\n{result}\n")
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    str
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    format!("\r{str}\n")
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                format!("{part}: ✖")
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                format!("\r{part}: ✖             \n")
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+/// Parse the arguments passed to `solve` and try to submit one part of the solution.
+/// The process exits with a non-zero code if the answer could not be submitted.
+/// Returns the verdict, or the refusal if the journal knows the answer is wrong.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) -> Option<Result<Verdict, Refusal>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +467,135 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answer = result.to_string();
+    let year = Year::of_binary();
+
+    // a journal that can not be read should not block submitting.
+    let journal = journal::load(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read submission journal: {e:?}");
+        Journal::default()
+    });
+
+    if let Err(refusal) = journal.check(day, part, &answer) {
+        println!("✘ Not submitting {answer}: {refusal}");
+        return Some(Err(refusal));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+
+    let mut retries = 0;
+
+    let verdict = loop {
+        let verdict = match aoc_client::submit(year, day, part, &answer) {
+            Ok(verdict) => verdict,
+            Err(e) => {
+                eprintln!("failed to submit result: {e}");
+                process::exit(1);
+            }
+        };
+
+        if let Err(e) = journal::record(year, Submission::new(day, part, &answer, verdict.clone()))
+        {
+            eprintln!("Failed to record submission in journal: {e:?}");
+        }
+
+        match verdict {
+            Verdict::RateLimited(wait) if retries < SUBMIT_RETRIES => {
+                retries += 1;
+                countdown(wait.unwrap_or(DEFAULT_RATE_LIMIT_WAIT) + RATE_LIMIT_MARGIN);
+            }
+            verdict => break verdict,
+        }
+    };
+
+    println!("{verdict}");
+
+    Some(Ok(verdict))
+}
+
+/// Number of times a rate-limited submission is retried.
+const SUBMIT_RETRIES: u8 = 3;
+/// Wait time if a rate-limited response does not state how long to wait.
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+/// Added to the wait time, so the retry does not race the end of the lockout.
+const RATE_LIMIT_MARGIN: Duration = Duration::from_secs(1);
+
+/// Waits for the rate limit to pass, counting down the remaining seconds.
+fn countdown(wait: Duration) {
+    let start = Instant::now();
+
+    while let Some(remaining) = wait.checked_sub(start.elapsed()) {
+        print!(
+            "\r⏱ Rate-limited, retrying in {}s... ",
+            remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+        );
+        let _ = stdout().flush();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!("\r⏱ Rate limit passed, retrying.        ");
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{exit_code, PartOutcome, PANIC_EXIT_CODE};
+    use crate::template::{
+        journal::Refusal,
+        verdict::{Verdict, RATE_LIMITED_EXIT_CODE, WRONG_ANSWER_EXIT_CODE},
+    };
+
+    fn outcome(panicked: bool, verdict: Option<Verdict>) -> PartOutcome {
+        PartOutcome {
+            panicked,
+            submission: verdict.map(Ok),
+        }
+    }
+
+    #[test]
+    fn exits_successfully() {
+        assert_eq!(
+            exit_code(&[outcome(false, None), outcome(false, None)]),
+            None
+        );
+        assert_eq!(
+            exit_code(&[outcome(false, Some(Verdict::Correct)), outcome(false, None)]),
+            None
+        );
+    }
+
+    #[test]
+    fn exits_with_panic_code() {
+        assert_eq!(
+            exit_code(&[outcome(true, None), outcome(false, None)]),
+            Some(PANIC_EXIT_CODE)
+        );
+    }
+
+    #[test]
+    fn exits_with_verdict_code() {
+        assert_eq!(
+            exit_code(&[outcome(false, Some(Verdict::TooLow)), outcome(true, None)]),
+            Some(WRONG_ANSWER_EXIT_CODE)
+        );
+        assert_eq!(
+            exit_code(&[
+                outcome(false, None),
+                outcome(false, Some(Verdict::RateLimited(None)))
+            ]),
+            Some(RATE_LIMITED_EXIT_CODE)
+        );
+    }
+
+    #[test]
+    fn exits_with_wrong_answer_code_when_refused() {
+        let refused = PartOutcome {
+            panicked: false,
+            submission: Some(Err(Refusal::AlreadyRejected)),
+        };
+
+        assert_eq!(
+            exit_code(&[refused, outcome(false, None)]),
+            Some(WRONG_ANSWER_EXIT_CODE)
+        );
+    }
 }
@@ -0,0 +1,165 @@
+/// Summary statistics over the samples collected while benching a solution part.
+use std::time::Duration;
+
+/// Tukey's fence factor used for outlier rejection. Only "far out" samples are rejected.
+const OUTLIER_FENCE: f64 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchStats {
+    /// Number of times the solution was executed (excluding warmup runs).
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+}
+
+impl BenchStats {
+    /// Statistics for a solution that was executed exactly once.
+    #[must_use]
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            outliers: 0,
+            mean: duration,
+            median: duration,
+            min: duration,
+            max: duration,
+            std_dev: Duration::ZERO,
+            p95: duration,
+            p99: duration,
+        }
+    }
+
+    /// Computes statistics from raw samples.
+    /// Samples outside of the inter-quartile fences are rejected before the report is computed,
+    /// so a single scheduler hiccup does not skew the result.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let retained = reject_outliers(&sorted);
+        let n = retained.len() as f64;
+
+        let mean = retained.iter().sum::<u128>() as f64 / n;
+
+        let std_dev = if retained.len() > 1 {
+            let variance = retained
+                .iter()
+                .map(|x| (*x as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1.0);
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            samples: sorted.len() as u128,
+            outliers: (sorted.len() - retained.len()) as u128,
+            mean: nanos(mean),
+            median: nanos(median(retained)),
+            min: nanos(retained[0] as f64),
+            max: nanos(retained[retained.len() - 1] as f64),
+            std_dev: nanos(std_dev),
+            p95: nanos(percentile(retained, 95.0) as f64),
+            p99: nanos(percentile(retained, 99.0) as f64),
+        })
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos(value: f64) -> Duration {
+    Duration::from_nanos(value.round() as u64)
+}
+
+/// Expects a sorted, non-empty slice.
+fn median(sorted: &[u128]) -> f64 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) as f64 / 2.0
+    } else {
+        sorted[mid] as f64
+    }
+}
+
+/// Nearest-rank percentile. Expects a sorted, non-empty slice.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn percentile(sorted: &[u128], p: f64) -> u128 {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Returns the sub-slice of samples within Tukey's fences. Expects a sorted, non-empty slice.
+fn reject_outliers(sorted: &[u128]) -> &[u128] {
+    // quartiles are not meaningful for tiny sample sets.
+    if sorted.len() < 4 {
+        return sorted;
+    }
+
+    let q1 = percentile(sorted, 25.0) as f64;
+    let q3 = percentile(sorted, 75.0) as f64;
+    let iqr = q3 - q1;
+    let lower = q1 - OUTLIER_FENCE * iqr;
+    let upper = q3 + OUTLIER_FENCE * iqr;
+
+    let start = sorted.partition_point(|x| (*x as f64) < lower);
+    let end = sorted.partition_point(|x| (*x as f64) <= upper);
+
+    &sorted[start..end]
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchStats;
+    use std::time::Duration;
+
+    fn ns(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn empty_samples() {
+        assert!(BenchStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn computes_summary() {
+        let stats = BenchStats::from_samples(&ns(&[10, 20, 30, 40, 50])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.std_dev, Duration::from_nanos(16));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        assert_eq!(stats.p99, Duration::from_nanos(50));
+    }
+
+    #[test]
+    fn median_of_even_samples() {
+        let stats = BenchStats::from_samples(&ns(&[40, 10, 30, 20])).unwrap();
+        assert_eq!(stats.median, Duration::from_nanos(25));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats =
+            BenchStats::from_samples(&ns(&[100, 101, 99, 100, 102, 98, 100, 5000])).unwrap();
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(102));
+        assert_eq!(stats.mean, Duration::from_nanos(100));
+    }
+}
@@ -0,0 +1,38 @@
+/// Helpers shared by the tests of the template modules.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::template::{
+    report::{PartReport, Status},
+    stats::BenchStats,
+};
+
+/// The point in time `secs` seconds after the unix epoch.
+#[must_use]
+pub fn at(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+/// Splits a command-line at spaces, e.g. `args("01 --time")`.
+#[must_use]
+pub fn args(s: &str) -> Vec<String> {
+    s.split(' ').map(String::from).collect()
+}
+
+/// The report of a part measured with the given samples in nanoseconds. Parts without an answer are unsolved,
+/// except for the parse step (part `0`), which has none.
+#[must_use]
+pub fn report(part: u8, answer: Option<&str>, expected: Option<&str>, nanos: &[u64]) -> PartReport {
+    let samples: Vec<Duration> = nanos.iter().map(|x| Duration::from_nanos(*x)).collect();
+    PartReport {
+        part,
+        answer: answer.map(String::from),
+        expected: expected.map(String::from),
+        status: if answer.is_some() || part == 0 {
+            Status::Ok
+        } else {
+            Status::Unsolved
+        },
+        stats: BenchStats::from_samples(&samples).unwrap(),
+        alloc: None,
+    }
+}
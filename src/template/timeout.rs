@@ -0,0 +1,182 @@
+/// Time limits for solution runs.
+/// A part limit applies to each part, including its benchmark samples with `--time`, a day limit to the whole run of a solution binary.
+/// Both can be passed on the command-line or configured through `AOC_PART_TIMEOUT` / `AOC_DAY_TIMEOUT` (in seconds).
+use std::{
+    env,
+    sync::{
+        mpsc::{self, RecvTimeoutError, Sender},
+        Once,
+    },
+    thread,
+    time::Duration,
+};
+
+const PART_TIMEOUT_ARG: &str = "--part-timeout";
+const DAY_TIMEOUT_ARG: &str = "--day-timeout";
+
+const PART_TIMEOUT_ENV: &str = "AOC_PART_TIMEOUT";
+const DAY_TIMEOUT_ENV: &str = "AOC_DAY_TIMEOUT";
+
+/// Exit code of a solution binary that was stopped by its watchdog.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Time limits in seconds. Unset limits fall back to the environment, then to no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeoutOptions {
+    pub part_secs: Option<u64>,
+    pub day_secs: Option<u64>,
+}
+
+impl TimeoutOptions {
+    /// Reads the limits from command-line arguments, e.g. `--part-timeout 10`.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        Ok(Self {
+            part_secs: parse_arg(args, PART_TIMEOUT_ARG)?,
+            day_secs: parse_arg(args, DAY_TIMEOUT_ARG)?,
+        })
+    }
+
+    /// Converts the limits back to command-line arguments, so they can be mirrored to child invocations.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(secs) = self.part_secs {
+            args.push(PART_TIMEOUT_ARG.into());
+            args.push(secs.to_string());
+        }
+
+        if let Some(secs) = self.day_secs {
+            args.push(DAY_TIMEOUT_ARG.into());
+            args.push(secs.to_string());
+        }
+
+        args
+    }
+
+    /// Fills unset limits from the environment.
+    #[must_use]
+    pub fn resolve(&self) -> Self {
+        Self {
+            part_secs: self.part_secs.or_else(|| parse_env(PART_TIMEOUT_ENV)),
+            day_secs: self.day_secs.or_else(|| parse_env(DAY_TIMEOUT_ENV)),
+        }
+    }
+
+    #[must_use]
+    pub fn part_limit(&self) -> Option<Duration> {
+        self.part_secs.map(Duration::from_secs)
+    }
+
+    #[must_use]
+    pub fn day_limit(&self) -> Option<Duration> {
+        self.day_secs.map(Duration::from_secs)
+    }
+}
+
+/// Guards the run of a part. If the guard is not dropped before the limit is reached,
+/// `on_timeout` runs on the watchdog thread. It is expected to terminate the process.
+pub struct Watchdog {
+    cancel: Sender<()>,
+}
+
+impl Watchdog {
+    pub fn start(limit: Duration, on_timeout: impl FnOnce(Duration) + Send + 'static) -> Self {
+        let (tx, rx) = mpsc::channel::<()>();
+
+        thread::spawn(move || {
+            if let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(limit) {
+                on_timeout(limit);
+            }
+        });
+
+        Self { cancel: tx }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        let _ = self.cancel.send(());
+    }
+}
+
+/// Starts a watchdog that stays armed until the process exits. Only the first call per process has an effect.
+pub fn arm_for_process(limit: Duration, on_timeout: impl FnOnce(Duration) + Send + 'static) {
+    static ARMED: Once = Once::new();
+
+    ARMED.call_once(|| {
+        thread::spawn(move || {
+            thread::sleep(limit);
+            on_timeout(limit);
+        });
+    });
+}
+
+fn parse_arg(args: &[String], name: &str) -> Result<Option<u64>, String> {
+    let Some(index) = args.iter().position(|x| x == name) else {
+        return Ok(None);
+    };
+
+    args.get(index + 1)
+        .and_then(|x| x.parse().ok())
+        .map(Some)
+        .ok_or_else(|| format!("expecting a number of seconds after {name}"))
+}
+
+fn parse_env(name: &str) -> Option<u64> {
+    env::var(name).ok()?.parse().ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{TimeoutOptions, Watchdog};
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    #[test]
+    fn roundtrips_args() {
+        let options = TimeoutOptions {
+            part_secs: Some(5),
+            day_secs: None,
+        };
+        assert_eq!(
+            TimeoutOptions::from_args(&options.to_args()).unwrap(),
+            options
+        );
+        assert!(TimeoutOptions::from_args(&["--day-timeout".into()]).is_err());
+    }
+
+    #[test]
+    fn cancelled_watchdog_does_not_fire() {
+        let fired = Arc::new(AtomicBool::new(false));
+        let flag = fired.clone();
+
+        let watchdog = Watchdog::start(Duration::from_millis(200), move |_| {
+            flag.store(true, Ordering::SeqCst);
+        });
+        drop(watchdog);
+
+        thread::sleep(Duration::from_millis(400));
+        assert!(!fired.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn watchdog_fires_after_limit() {
+        let fired = Arc::new(AtomicBool::new(false));
+        let flag = fired.clone();
+
+        let _watchdog = Watchdog::start(Duration::from_millis(200), move |limit| {
+            assert_eq!(limit, Duration::from_millis(200));
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        thread::sleep(Duration::from_millis(400));
+        assert!(fired.load(Ordering::SeqCst));
+    }
+}
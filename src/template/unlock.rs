@@ -0,0 +1,156 @@
+/// Computes when puzzles unlock: midnight US Eastern (UTC−5) on December 1 to 25.
+/// Time is read through the [`Clock`] trait, so waiting can be tested without waiting for real time.
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{all_days, Day};
+
+/// Puzzles unlock at midnight US Eastern, which is 05:00 UTC in December.
+const UNLOCK_HOUR_UTC: u64 = 5;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real system clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// The time the puzzle of a day unlocks.
+#[must_use]
+pub fn unlock_time(year: u16, day: Day) -> SystemTime {
+    let days = days_from_civil(i64::from(year), 12, u64::from(day.into_inner()));
+    let days = u64::try_from(days).expect("years before 1970 are not supported");
+    UNIX_EPOCH + Duration::from_secs(days * SECONDS_PER_DAY + UNLOCK_HOUR_UTC * 60 * 60)
+}
+
+/// The next day of a year that unlocks after `now`. `None` once all days are unlocked.
+#[must_use]
+pub fn next_unlock(year: u16, now: SystemTime) -> Option<(Day, SystemTime)> {
+    all_days()
+        .map(|day| (day, unlock_time(year, day)))
+        .find(|(_, time)| *time > now)
+}
+
+/// Waits until `target`, calling `on_tick` with the remaining time about once per second.
+pub fn wait_until(clock: &impl Clock, target: SystemTime, mut on_tick: impl FnMut(Duration)) {
+    while let Ok(remaining) = target.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+
+        on_tick(remaining);
+        clock.sleep(remaining.min(Duration::from_secs(1)));
+    }
+}
+
+/// Formats a remaining time, e.g. `2d 03h 04m 05s`.
+#[must_use]
+pub fn format_remaining(remaining: Duration) -> String {
+    // round up, so the countdown does not show zero before the target is reached.
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let (days, hours, minutes, seconds) = (
+        secs / SECONDS_PER_DAY,
+        secs % SECONDS_PER_DAY / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}h {minutes:02}m {seconds:02}s")
+    } else {
+        format!("{hours:02}h {minutes:02}m {seconds:02}s")
+    }
+}
+
+/// Days since the unix epoch of a date in the proleptic gregorian calendar.
+fn days_from_civil(year: i64, month: u64, day: u64) -> i64 {
+    // shift the year to start in march, so the leap day is the last day of a year.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = i64::try_from((153 * ((month + 9) % 12) + 2) / 5 + day - 1).unwrap();
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_remaining, next_unlock, unlock_time, wait_until, Clock};
+    use crate::day;
+    use crate::template::test_helpers::at;
+    use std::{
+        cell::Cell,
+        time::{Duration, SystemTime},
+    };
+
+    /// A clock that only advances when sleeping.
+    struct FakeClock(Cell<SystemTime>);
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.0.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.0.set(self.0.get() + duration);
+        }
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(unlock_time(2023, day!(1)), at(1_701_406_800));
+        // 2015-12-25T05:00:00Z
+        assert_eq!(unlock_time(2015, day!(25)), at(1_451_019_600));
+        // 2024 is a leap year: 2024-12-01T05:00:00Z
+        assert_eq!(unlock_time(2024, day!(1)), at(1_733_029_200));
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        assert_eq!(
+            next_unlock(2023, at(1_700_000_000)),
+            Some((day!(1), at(1_701_406_800)))
+        );
+        // one second after day 1 unlocked.
+        assert_eq!(
+            next_unlock(2023, at(1_701_406_801)),
+            Some((day!(2), at(1_701_493_200)))
+        );
+        assert_eq!(next_unlock(2023, at(1_704_067_200)), None);
+    }
+
+    #[test]
+    fn waits_until_target() {
+        let clock = FakeClock(Cell::new(at(100)));
+        let mut ticks = vec![];
+
+        wait_until(&clock, at(103), |remaining| ticks.push(remaining.as_secs()));
+
+        assert_eq!(ticks, vec![3, 2, 1]);
+        assert_eq!(clock.now(), at(103));
+    }
+
+    #[test]
+    fn formats_remaining_time() {
+        assert_eq!(format_remaining(Duration::from_millis(1500)), "00h 00m 02s");
+        assert_eq!(
+            format_remaining(Duration::from_secs(2 * 86_400 + 3 * 3600 + 4 * 60 + 5)),
+            "2d 03h 04m 05s"
+        );
+    }
+}
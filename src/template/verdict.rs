@@ -0,0 +1,183 @@
+/// The outcome of submitting an answer, parsed from the message of the Advent of Code website.
+use std::{fmt::Display, time::Duration};
+
+/// Exit code of a solution whose submitted answer was rejected.
+pub const WRONG_ANSWER_EXIT_CODE: i32 = 1;
+/// Exit code of a solution whose answer could not be submitted because of the rate limit.
+pub const RATE_LIMITED_EXIT_CODE: i32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// The answer is wrong, without a hint in which direction.
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently. Holds the time left to wait, if the message contains it.
+    RateLimited(Option<Duration>),
+    /// The part was solved already, or is not unlocked yet.
+    AlreadySolved,
+    /// A message that could not be classified.
+    Unknown(String),
+}
+
+impl Verdict {
+    #[must_use]
+    pub fn parse(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if message.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::RateLimited(parse_wait(message))
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown(message.trim().to_string())
+        }
+    }
+
+    /// Name of the verdict in stored files. Wait times and messages are not kept.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::RateLimited(_) => "rate_limited",
+            Verdict::AlreadySolved => "already_solved",
+            Verdict::Unknown(_) => "unknown",
+        }
+    }
+
+    pub(crate) fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "correct" => Some(Verdict::Correct),
+            "wrong" => Some(Verdict::Wrong),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "rate_limited" => Some(Verdict::RateLimited(None)),
+            "already_solved" => Some(Verdict::AlreadySolved),
+            "unknown" => Some(Verdict::Unknown(String::new())),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+
+    /// The exit code a solution binary ends with after submitting.
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Verdict::Correct | Verdict::AlreadySolved => 0,
+            Verdict::RateLimited(_) => RATE_LIMITED_EXIT_CODE,
+            Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow | Verdict::Unknown(_) => {
+                WRONG_ANSWER_EXIT_CODE
+            }
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "✔ That's the right answer!"),
+            Verdict::Wrong => write!(f, "✘ That's not the right answer."),
+            Verdict::TooHigh => write!(f, "✘ That's not the right answer, it is too high."),
+            Verdict::TooLow => write!(f, "✘ That's not the right answer, it is too low."),
+            Verdict::RateLimited(Some(wait)) => {
+                write!(f, "⏱ Rate-limited, try again in {}s.", wait.as_secs())
+            }
+            Verdict::RateLimited(None) => write!(f, "⏱ Rate-limited, try again later."),
+            Verdict::AlreadySolved => write!(f, "This part is solved already or not unlocked yet."),
+            Verdict::Unknown(message) => write!(f, "? Unexpected response: {message}"),
+        }
+    }
+}
+
+/// Parses the wait time of messages like `You have 1m 4s left to wait.`
+fn parse_wait(message: &str) -> Option<Duration> {
+    let start = message.find("You have ")? + "You have ".len();
+    let end = start + message[start..].find(" left to wait")?;
+
+    message[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Verdict;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::parse("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too high. Please wait one minute before trying again."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer. If you're stuck, make sure you're using the full input data."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert!(matches!(Verdict::parse("Hello"), Verdict::Unknown(_)));
+    }
+
+    #[test]
+    fn parses_wait_time() {
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 4s left to wait."),
+            Verdict::RateLimited(Some(Duration::from_secs(64)))
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 34s left to wait."),
+            Verdict::RateLimited(Some(Duration::from_secs(34)))
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently."),
+            Verdict::RateLimited(None)
+        );
+    }
+
+    #[test]
+    fn maps_exit_codes() {
+        assert_eq!(Verdict::Correct.exit_code(), 0);
+        assert!(Verdict::TooLow.is_wrong());
+        assert_ne!(Verdict::TooLow.exit_code(), 0);
+        assert_ne!(Verdict::RateLimited(None).exit_code(), 0);
+    }
+}
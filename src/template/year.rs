@@ -0,0 +1,197 @@
+/// Selects the year a command works on and where its files live.
+/// The configured year (`AOC_YEAR` in `.cargo/config.toml`) uses the top-level layout, e.g. `src/bin/01.rs` and
+/// `data/inputs/01.txt`. Other years are namespaced, e.g. `src/bin/2022-01.rs` and `data/2022/inputs/01.txt`,
+/// so a running solution (or its tests) can tell its year from the name of its executable.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::Day;
+
+pub const YEAR_ENV: &str = "AOC_YEAR";
+pub const CONFIG_FILE: &str = ".cargo/config.toml";
+
+/// The year of a command. Wraps `None` for the configured year.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Year(Option<u16>);
+
+impl Year {
+    /// Selects a year. The configured year and `None` select the top-level layout.
+    #[must_use]
+    pub fn new(year: Option<u16>) -> Self {
+        match year {
+            Some(year) if Some(year) != configured() => Self(Some(year)),
+            _ => Self(None),
+        }
+    }
+
+    /// Selects the namespaced layout of a year, even if it is the configured one, e.g. to archive it.
+    #[must_use]
+    pub fn namespaced(year: u16) -> Self {
+        Self(Some(year))
+    }
+
+    /// The year of the running solution binary, derived from its executable name.
+    #[must_use]
+    pub fn of_binary() -> Self {
+        let name = env::current_exe()
+            .ok()
+            .and_then(|path| Some(path.file_stem()?.to_string_lossy().to_string()));
+
+        Self::new(name.as_deref().and_then(parse_bin_year))
+    }
+
+    /// The year number, falling back to the configured year.
+    #[must_use]
+    pub fn number(&self) -> Option<u16> {
+        self.0.or_else(configured)
+    }
+
+    #[must_use]
+    pub fn is_namespaced(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Root folder of puzzle data, e.g. `data` or `data/2022`.
+    #[must_use]
+    pub fn data_dir(&self) -> PathBuf {
+        match self.0 {
+            Some(year) => Path::new("data").join(year.to_string()),
+            None => PathBuf::from("data"),
+        }
+    }
+
+    /// Path of a day's file in a data folder, e.g. `data/2022/inputs/01.txt`.
+    #[must_use]
+    pub fn data_path(&self, folder: &str, file: &str) -> PathBuf {
+        self.data_dir().join(folder).join(file)
+    }
+
+    /// Name of a day's solution binary, e.g. `01` or `2022-01`.
+    #[must_use]
+    pub fn bin_name(&self, day: Day) -> String {
+        match self.0 {
+            Some(year) => format!("{year}-{day}"),
+            None => day.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn bin_path(&self, day: Day) -> String {
+        format!("./src/bin/{}.rs", self.bin_name(day))
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.number() {
+            Some(year) => write!(f, "{year}"),
+            None => write!(f, "current year"),
+        }
+    }
+}
+
+/// The year configured through `AOC_YEAR`.
+#[must_use]
+pub fn configured() -> Option<u16> {
+    env::var(YEAR_ENV).ok()?.parse().ok()
+}
+
+/// Reads `.cargo/config.toml` and sets the configured year in its contents, without writing the file.
+/// Returns the current and the updated contents. Fails if the file does not configure a year.
+pub fn read_config_with(year: u16) -> io::Result<(String, String)> {
+    let contents = fs::read_to_string(CONFIG_FILE)?;
+
+    let updated = replace_configured(&contents, year).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("the file does not set {YEAR_ENV}"),
+        )
+    })?;
+
+    Ok((contents, updated))
+}
+
+/// Replaces the value of the `AOC_YEAR` line of a config file. Commented lines are left alone.
+fn replace_configured(contents: &str, year: u16) -> Option<String> {
+    let mut found = false;
+
+    let lines: Vec<String> = contents
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let is_year = trimmed
+                .strip_prefix(YEAR_ENV)
+                .is_some_and(|rest| rest.trim_start().starts_with('='));
+
+            if is_year && !found {
+                found = true;
+                let indent = &line[..line.len() - trimmed.len()];
+                format!("{indent}{YEAR_ENV} = \"{year}\"")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    found.then(|| lines.join("\n") + if contents.ends_with('\n') { "\n" } else { "" })
+}
+
+/// Parses the year of a binary or test executable name, e.g. `2022-01` or `2022_01-1a2b3c`.
+fn parse_bin_year(name: &str) -> Option<u16> {
+    let (year, rest) = name.split_at_checked(4)?;
+    let day = rest.strip_prefix(['-', '_'])?.get(..2)?;
+
+    if !day.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    year.parse().ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_bin_year, replace_configured, Year};
+    use crate::day;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_bin_year() {
+        assert_eq!(parse_bin_year("2022-01"), Some(2022));
+        assert_eq!(parse_bin_year("2015_25-9f8e7d6c"), Some(2015));
+        assert_eq!(parse_bin_year("01"), None);
+        assert_eq!(parse_bin_year("01-3a4b5c"), None);
+        assert_eq!(parse_bin_year("advent_of_code"), None);
+    }
+
+    #[test]
+    fn namespaces_other_years() {
+        let year = Year(Some(2015));
+        assert_eq!(year.bin_name(day!(3)), "2015-03");
+        assert_eq!(year.bin_path(day!(3)), "./src/bin/2015-03.rs");
+        assert_eq!(
+            year.data_path("inputs", "03.txt"),
+            PathBuf::from("data/2015/inputs/03.txt")
+        );
+
+        let current = Year(None);
+        assert_eq!(current.bin_name(day!(3)), "03");
+        assert_eq!(
+            current.data_path("inputs", "03.txt"),
+            PathBuf::from("data/inputs/03.txt")
+        );
+    }
+
+    #[test]
+    fn replaces_configured_year() {
+        let config = "[env]\n# AOC_YEAR = \"2015\"\nAOC_YEAR = \"2023\"\nAOC_YEAR_X = \"1\"\n";
+        assert_eq!(
+            replace_configured(config, 2024).as_deref(),
+            Some("[env]\n# AOC_YEAR = \"2015\"\nAOC_YEAR = \"2024\"\nAOC_YEAR_X = \"1\"\n")
+        );
+        assert_eq!(replace_configured("[env]\n", 2024), None);
+    }
+}
@@ -0,0 +1,63 @@
+//! Day {{day}}: {{title}}
+advent_of_code::solution!({{day}}, parse_grid);
+
+pub struct Grid {
+    cells: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid {
+    /// Reads a cell, `None` outside of the grid.
+    #[allow(dead_code)]
+    fn get(&self, x: usize, y: usize) -> Option<u8> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+}
+
+pub fn parse_grid(input: &str) -> Grid {
+    let lines: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+    let width = lines.first().map_or(0, |line| line.len());
+
+    Grid {
+        cells: lines.iter().flat_map(|line| line.bytes()).collect(),
+        width,
+        height: lines.len(),
+    }
+}
+
+pub fn part_one(grid: &Grid) -> Option<u32> {
+    None
+}
+
+pub fn part_two(grid: &Grid) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid {
+        parse_grid(&advent_of_code::template::read_file("examples", DAY))
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let grid = example();
+        assert_eq!(grid.cells.len(), grid.width * grid.height);
+        assert_eq!(grid.get(grid.width, 0), None);
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&example());
+        assert_eq!(result, {{example_part_one}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&example());
+        assert_eq!(result, {{example_part_two}});
+    }
+}
@@ -0,0 +1,42 @@
+//! Day {{day}}: {{title}}
+advent_of_code::solution!({{day}}, parse_input);
+
+pub struct Input {
+    #[allow(dead_code)]
+    lines: Vec<String>,
+}
+
+pub fn parse_input(input: &str) -> Input {
+    Input {
+        lines: input.lines().map(String::from).collect(),
+    }
+}
+
+pub fn part_one(input: &Input) -> Option<u32> {
+    None
+}
+
+pub fn part_two(input: &Input) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
+        assert_eq!(result, {{example_part_one}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
+        assert_eq!(result, {{example_part_two}});
+    }
+}